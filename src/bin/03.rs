@@ -1,48 +1,49 @@
+use advent_of_code::parse::{self, ParseError};
+
 advent_of_code::solution!(3);
 
-pub fn part_one(input: &str) -> Option<u32> {
+pub fn part_one(input: &str) -> Result<u32, ParseError> {
     // /!\ Input has been pre-processed using grep/sed /!\
     //
     // grep -o "mul([0-9]*,[0-9]*)" 03_og.txt | sed 's/mul(\(.*\))/\1/' > 03-1.txt
     let mut sum = 0;
-    for line in input.lines() {
+    for (idx, line) in input.lines().enumerate() {
         match line {
             "do()" => {}
             "don't()" => {}
             val => {
-                sum = sum + parse_mul(val);
+                sum += parse_mul(val).map_err(|e| e.at_line(idx + 1))?;
             }
         }
     }
 
-    Some(sum.try_into().unwrap())
+    Ok(sum.try_into().unwrap())
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
+pub fn part_two(input: &str) -> Result<u32, ParseError> {
     // /!\ Input has been pre-processed using grep/sed /!\
     //
     // grep -o "do()\|don't()\|mul([0-9]*,[0-9]*)" 03_og.txt | sed 's/mul(\(.*\))/\1/' > 03-2.txt
     let mut enabled = true;
     let mut sum = 0;
-    for line in input.lines() {
+    for (idx, line) in input.lines().enumerate() {
         match line {
             "do()" => enabled = true,
             "don't()" => enabled = false,
             val if enabled => {
-                let res = parse_mul(val);
-                sum = sum + res;
+                let res = parse_mul(val).map_err(|e| e.at_line(idx + 1))?;
+                sum += res;
             }
             _ => {}
         }
     }
 
-    Some(sum.try_into().unwrap())
+    Ok(sum.try_into().unwrap())
 }
 
-pub fn parse_mul(line: &str) -> i32 {
-    let nums: Vec<i32> = line.split(",").map(|s| s.parse::<i32>().unwrap()).collect();
-    let res = nums.first().unwrap() * nums.last().unwrap();
-    res
+pub fn parse_mul(line: &str) -> Result<i32, ParseError> {
+    let nums: Vec<i32> = parse::scan("{},{}", line)?;
+    Ok(nums[0] * nums[1])
 }
 
 #[cfg(test)]
@@ -54,7 +55,7 @@ mod tests {
         let result = part_one(&advent_of_code::template::read_file_part(
             "examples", DAY, 1,
         ));
-        assert_eq!(result, Some(161));
+        assert_eq!(result, Ok(161));
     }
 
     #[test]
@@ -62,6 +63,6 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file_part(
             "examples", DAY, 2,
         ));
-        assert_eq!(result, Some(48));
+        assert_eq!(result, Ok(48));
     }
 }
@@ -1,6 +1,6 @@
-use core::panic;
-
+use advent_of_code::parse::{self, ParseError};
 use advent_of_code::{Direction, Pos2D};
+use itertools::Itertools;
 
 advent_of_code::solution!(6);
 
@@ -37,34 +37,35 @@ fn get_in_front_pos(pos: &Pos2D, facing: &Direction) -> Pos2D {
     }
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let mut start_pos: Option<Pos2D> = None;
-
-    let mut obstacles: Vec<Pos2D> = Vec::new();
-
-    let height = input.lines().count();
-    let width = input.lines().last().unwrap().chars().count();
+/// Returns the guard starting position, the obstacles and the size of the lab.
+fn parse_lab(input: &str) -> Result<(Pos2D, Vec<Pos2D>, usize, usize), ParseError> {
+    let lab = parse::grid(input, |tile| {
+        matches!(tile, '.' | '#' | '^').then_some(tile)
+    })?;
 
-    for (y, line) in input.lines().enumerate() {
-        for (x, tile) in line.chars().enumerate() {
-            let x: i32 = x.try_into().unwrap();
-            let y: i32 = y.try_into().unwrap();
-
-            match tile {
-                '.' => {}
-                '#' => obstacles.push(Pos2D { x, y }),
-                '^' => {
-                    assert!(start_pos.is_none(), "start_pos cannot be already set!");
-                    start_pos = Some(Pos2D { x, y })
-                }
-                c => panic!("Unknown character: {}", c),
-            }
-        }
+    let tiles_at = |kind: char| -> Vec<Pos2D> {
+        lab.tiles
+            .iter()
+            .positions(|&tile| tile == kind)
+            .map(|idx| lab.idx2pos(idx))
+            .collect()
+    };
+
+    let guards = tiles_at('^');
+    if guards.len() != 1 {
+        let line = guards.get(1).map_or(lab.height, |pos| pos.y as usize + 1);
+        return Err(ParseError::new(
+            line,
+            1,
+            format!("expected exactly one guard `^`, found {}", guards.len()),
+        ));
     }
 
-    assert!(start_pos.is_some(), "start_pos should have been set!");
+    Ok((guards[0], tiles_at('#'), lab.width, lab.height))
+}
 
-    let start_pos = start_pos.unwrap();
+pub fn part_one(input: &str) -> Result<u32, ParseError> {
+    let (start_pos, obstacles, width, height) = parse_lab(input)?;
 
     let mut current_pos = start_pos.clone();
     let mut facing = Direction::Up;
@@ -96,37 +97,11 @@ pub fn part_one(input: &str) -> Option<u32> {
     }
 
     // println!("{:#?}", visited);
-    Some(visited.len().try_into().unwrap())
+    Ok(visited.len().try_into().unwrap())
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let mut start_pos: Option<Pos2D> = None;
-
-    let mut obstacles: Vec<Pos2D> = Vec::new();
-
-    let height = input.lines().count();
-    let width = input.lines().last().unwrap().chars().count();
-
-    for (y, line) in input.lines().enumerate() {
-        for (x, tile) in line.chars().enumerate() {
-            let x: i32 = x.try_into().unwrap();
-            let y: i32 = y.try_into().unwrap();
-
-            match tile {
-                '.' => {}
-                '#' => obstacles.push(Pos2D { x, y }),
-                '^' => {
-                    assert!(start_pos.is_none(), "start_pos cannot be already set!");
-                    start_pos = Some(Pos2D { x, y })
-                }
-                c => panic!("Unknown character: {}", c),
-            }
-        }
-    }
-
-    assert!(start_pos.is_some(), "start_pos should have been set!");
-
-    let start_pos = start_pos.unwrap();
+pub fn part_two(input: &str) -> Result<u32, ParseError> {
+    let (start_pos, obstacles, width, height) = parse_lab(input)?;
 
     let mut facing = Direction::Up;
     let mut current_pos = start_pos.clone();
@@ -209,7 +184,7 @@ pub fn part_two(input: &str) -> Option<u32> {
         }
     }
 
-    Some(loop_candidates.len().try_into().unwrap())
+    Ok(loop_candidates.len().try_into().unwrap())
 }
 
 #[cfg(test)]
@@ -219,12 +194,12 @@ mod tests {
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(41));
+        assert_eq!(result, Ok(41));
     }
 
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(6));
+        assert_eq!(result, Ok(6));
     }
 }
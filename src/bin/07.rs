@@ -1,6 +1,7 @@
 use core::panic;
 use std::usize;
 
+use advent_of_code::math;
 use itertools::Itertools;
 
 advent_of_code::solution!(7);
@@ -86,8 +87,9 @@ pub fn part_two(input: &str) -> Option<u64> {
                     match operator {
                         '+' => equation_res += operand,
                         '*' => equation_res *= operand,
+                        // an overflow is above any expected result.
                         '|' => {
-                            equation_res = format!("{}{}", equation_res, operand).parse().unwrap()
+                            equation_res = math::concat(equation_res, *operand).unwrap_or(u64::MAX)
                         }
                         val => panic!("Unknown operator: {}", val),
                     }
@@ -1,3 +1,5 @@
+use advent_of_code::math;
+
 advent_of_code::solution!(8);
 
 struct Map {
@@ -101,41 +103,24 @@ fn find_antinodes_2(map: &Map) -> Vec<(i32, i32)> {
             .filter(|a| a.frequency == antenna.frequency && a.id != antenna.id);
 
         for reso in resonant_antennas {
-            let dx = antenna.x - reso.x;
-            let dy = antenna.y - reso.y;
-
-            let mut antinode = (antenna.x + dx, antenna.y + dy);
-            loop {
-                if in_bounds(antinode.0, antinode.1, map) {
+            // the smallest step between grid positions on the line through both antennas.
+            let divisor =
+                math::gcd((antenna.x - reso.x).into(), (antenna.y - reso.y).into()) as i32;
+            let dx = (antenna.x - reso.x) / divisor;
+            let dy = (antenna.y - reso.y) / divisor;
+
+            for (step_x, step_y) in [(dx, dy), (-dx, -dy)] {
+                let mut antinode = (antenna.x, antenna.y);
+                while in_bounds(antinode.0, antinode.1, map) {
                     if !antinodes
                         .iter()
                         .any(|an: &(i32, i32)| an.0 == antinode.0 && an.1 == antinode.1)
                     {
                         antinodes.push(antinode);
-                        // println!("{:?}", antinode);
                     }
-                } else {
-                    break;
-                }
-
-                antinode = (antinode.0 + dx, antinode.1 + dy);
-            }
 
-            antinode = (antenna.x - dx, antenna.y - dy);
-            loop {
-                if in_bounds(antinode.0, antinode.1, map) {
-                    if !antinodes
-                        .iter()
-                        .any(|an: &(i32, i32)| an.0 == antinode.0 && an.1 == antinode.1)
-                    {
-                        antinodes.push(antinode);
-                        // println!("{:?}", antinode);
-                    }
-                } else {
-                    break;
+                    antinode = (antinode.0 + step_x, antinode.1 + step_y);
                 }
-
-                antinode = (antinode.0 - dx, antinode.1 - dy);
             }
         }
     }
@@ -1,4 +1,8 @@
-use std::{collections::HashMap, usize};
+use std::usize;
+
+use advent_of_code::debug;
+use advent_of_code::math;
+use advent_of_code::memoize::Memo;
 
 advent_of_code::solution!(11);
 
@@ -15,12 +19,8 @@ fn blink(stones: &Vec<u64>) -> Vec<u64> {
     for stone in stones.iter() {
         match stone {
             0 => res.push(1),
-            val if val.to_string().len() % 2 == 0 => {
-                let str_val = val.to_string();
-                let (left, right) = str_val.split_at(str_val.len() / 2);
-
-                let left = left.parse::<u64>().unwrap();
-                let right = right.parse::<u64>().unwrap();
+            val if math::num_digits(*val).is_multiple_of(2) => {
+                let (left, right) = math::split_digits(*val, math::num_digits(*val) / 2);
 
                 res.push(left);
                 res.push(right);
@@ -31,52 +31,39 @@ fn blink(stones: &Vec<u64>) -> Vec<u64> {
     res
 }
 
-fn count_blink(stone: u64, iteration: u32, cache: &mut HashMap<(u64, u32), usize>) -> usize {
-    if let Some(&count) = cache.get(&(stone, iteration)) {
-        return count;
-    }
-
+fn count_blink(memo: &mut Memo<(u64, u32), usize>, stone: u64, iteration: u32) -> usize {
     if iteration == 0 {
         return 1;
     }
 
-    let count = match stone {
-        0 => count_blink(1, iteration - 1, cache),
-        val if val.to_string().len() % 2 == 0 => {
-            let str_val = val.to_string();
-            let (left, right) = str_val.split_at(str_val.len() / 2);
-
-            let left = left.parse::<u64>().unwrap();
-            let right = right.parse::<u64>().unwrap();
-
-            count_blink(left, iteration - 1, cache) + count_blink(right, iteration - 1, cache)
+    memo.get_or_compute((stone, iteration), |memo| match stone {
+        0 => count_blink(memo, 1, iteration - 1),
+        val if math::num_digits(val).is_multiple_of(2) => {
+            let (left, right) = math::split_digits(val, math::num_digits(val) / 2);
+            count_blink(memo, left, iteration - 1) + count_blink(memo, right, iteration - 1)
         }
-        val => count_blink(val * 2024, iteration - 1, cache),
-    };
+        val => count_blink(memo, val * 2024, iteration - 1),
+    })
+}
 
-    if cache.get(&(stone, iteration)).is_none() {
-        cache.insert((stone, iteration), count);
-    }
+fn count_stones(input: &str, blinks: u32) -> usize {
+    let mut memo = Memo::new();
+    let count = init_stones(input)
+        .iter()
+        .fold(0, |acc, &stone| acc + count_blink(&mut memo, stone, blinks));
 
+    if debug::Options::from_env().stats {
+        println!("Blink cache: {}", memo.stats());
+    }
     count
 }
 
 pub fn part_one(input: &str) -> Option<usize> {
-    let mut cache: HashMap<(u64, u32), usize> = HashMap::new();
-    Some(
-        init_stones(input)
-            .iter()
-            .fold(0, |acc, &stone| acc + count_blink(stone, 25, &mut cache)),
-    )
+    Some(count_stones(input, 25))
 }
 
 pub fn part_two(input: &str) -> Option<usize> {
-    let mut cache: HashMap<(u64, u32), usize> = HashMap::new();
-    Some(
-        init_stones(input)
-            .iter()
-            .fold(0, |acc, &stone| acc + count_blink(stone, 75, &mut cache)),
-    )
+    Some(count_stones(input, 75))
 }
 
 #[cfg(test)]
@@ -1,5 +1,12 @@
 use std::usize;
 
+use advent_of_code::cycle;
+use advent_of_code::math;
+use advent_of_code::parse::{self, ParseError};
+use advent_of_code::template::runner::Answer;
+use advent_of_code::viz::{Cell, Color, Frame, Visualizer};
+use advent_of_code::Pos2D;
+
 advent_of_code::solution!(14);
 
 #[derive(Debug)]
@@ -10,31 +17,47 @@ struct Map {
 }
 
 impl Map {
-    fn from_input(input: &str) -> Map {
-        let mut lines = input.lines();
-        let mut robots = Vec::new();
-
-        let size = parse_i32_vec2(lines.next().unwrap());
-        for line in lines {
-            let robot = Robot::from_input(line);
-            robots.push(robot);
-        }
+    fn from_input(input: &str) -> Result<Map, ParseError> {
+        let (size_line, robots_input) = input.split_once('\n').unwrap_or((input, ""));
+
+        let size: Vec<usize> = parse::scan("size={},{}", size_line)?;
+        let robots =
+            parse::lines_with(robots_input, Robot::from_input).map_err(|e| e.offset_lines(1))?;
 
-        Map {
-            width: size.0.try_into().unwrap(),
-            height: size.1.try_into().unwrap(),
+        Ok(Map {
+            width: size[0],
+            height: size[1],
             robots,
-        }
+        })
     }
 
     fn update_robots(&mut self, elapsed_time: u32) {
-        for _ in 0..elapsed_time {
-            for robot in self.robots.iter_mut() {
-                robot.move_it(self.width, self.height);
-            }
+        // every robot is back to its initial position after each period.
+        for _ in 0..i64::from(elapsed_time) % self.period() {
+            self.step();
+        }
+    }
+
+    fn step(&mut self) {
+        for robot in self.robots.iter_mut() {
+            robot.move_it(self.width, self.height);
         }
     }
 
+    fn positions(&self) -> Vec<(i32, i32)> {
+        self.robots
+            .iter()
+            .map(|robot| (robot.position.x, robot.position.y))
+            .collect()
+    }
+
+    /// Seconds until every robot is back to its initial position.
+    fn period(&self) -> i64 {
+        self.robots.iter().fold(1, |period, robot| {
+            math::lcm(period, robot.period(self.width, self.height))
+        })
+    }
+
     fn is_top_left_quad(&self, position: &Pos) -> bool {
         let quad_start = (0, 0);
         let quad_end = ((self.width as i32 - 1) / 2, (self.height as i32 - 1) / 2);
@@ -100,6 +123,21 @@ impl Map {
         quadrants
     }
 
+    fn to_frame(&self) -> Frame {
+        let positions: Vec<Pos2D> = self
+            .robots
+            .iter()
+            .map(|robot| Pos2D::new(robot.position.x, robot.position.y))
+            .collect();
+
+        Frame::from_positions(
+            self.width,
+            self.height,
+            &positions,
+            Cell::new('#', Color::Green),
+        )
+    }
+
     fn get_safety_factor(&self) -> u32 {
         self.count_robots_in_quadrants()
             .iter()
@@ -115,18 +153,24 @@ struct Robot {
 }
 
 impl Robot {
-    fn from_input(input: &str) -> Robot {
-        let parts: Vec<&str> = input.split(" ").collect();
-
-        let position = parse_i32_vec2(parts.first().unwrap());
-        let position = Pos {
-            x: position.0,
-            y: position.1,
-        };
-
-        let velocity = parse_i32_vec2(parts.last().unwrap());
+    fn from_input(input: &str) -> Result<Robot, ParseError> {
+        let values: Vec<i32> = parse::scan("p={},{} v={},{}", input)?;
+
+        Ok(Robot {
+            position: Pos {
+                x: values[0],
+                y: values[1],
+            },
+            velocity: (values[2], values[3]),
+        })
+    }
 
-        Robot { position, velocity }
+    /// Seconds until the robot is back to its position, each axis repeating on its own.
+    fn period(&self, map_width: usize, map_height: usize) -> i64 {
+        let (width, height) = (map_width as i64, map_height as i64);
+        let period_x = width / math::gcd(self.velocity.0.into(), width);
+        let period_y = height / math::gcd(self.velocity.1.into(), height);
+        math::lcm(period_x, period_y)
     }
 
     fn move_it(&mut self, map_width: usize, map_height: usize) {
@@ -144,29 +188,31 @@ struct Pos {
     y: i32,
 }
 
-fn parse_i32_vec2(input: &str) -> (i32, i32) {
-    let parts: Vec<&str> = input.split(",").collect();
-    let first = parts
-        .first()
-        .unwrap()
-        .split("=")
-        .last()
-        .unwrap()
-        .parse::<i32>()
-        .unwrap();
-
-    let second = parts.last().unwrap().parse::<i32>().unwrap();
-    (first, second)
-}
-
-pub fn part_one(input: &str) -> Option<u32> {
-    let mut map = Map::from_input(input);
+pub fn part_one(input: &str) -> Result<u32, ParseError> {
+    let mut map = Map::from_input(input)?;
     map.update_robots(100);
-    Some(map.get_safety_factor())
+    Ok(map.get_safety_factor())
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    None
+pub fn part_two(input: &str) -> Result<Answer<u32>, ParseError> {
+    let mut map = Map::from_input(input)?;
+    let mut viz = Visualizer::from_args(DAY);
+    if viz.is_enabled() {
+        // each state is shown before stepping away from it, so the search stops before showing
+        // robots back to positions already shown.
+        let mut elapsed = 0;
+        cycle::find_cycle(
+            &mut map,
+            |map| {
+                viz.show(&map.to_frame(), format!("{elapsed}s elapsed"));
+                map.step();
+                elapsed += 1;
+            },
+            Map::positions,
+        );
+    }
+
+    Ok(Answer::NotImplemented)
 }
 
 #[cfg(test)]
@@ -176,12 +222,12 @@ mod tests {
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(12));
+        assert_eq!(result, Ok(12));
     }
 
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
+        assert_eq!(result, Ok(Answer::NotImplemented));
     }
 }
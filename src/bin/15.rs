@@ -1,5 +1,6 @@
 use core::panic;
 
+use advent_of_code::viz::{Cell, Color, Frame, Visualizer};
 use advent_of_code::{pos2idx, Map2D, Pos2D};
 use itertools::Itertools;
 
@@ -76,25 +77,18 @@ impl Map {
         }
     }
 
-    fn display(&self) {
-        for y in 0..self.base.height {
-            for x in 0..self.base.width {
-                let pos = Pos2D::new(x as i32, y as i32);
-                let tile = self.base.get(&pos).unwrap();
-                if self.robot_pos.x == pos.x && self.robot_pos.y == pos.y {
-                    print!("@");
-                } else {
-                    let c = match tile {
-                        TileType::Box => 'O',
-                        TileType::Wall => '#',
-                        TileType::Floor => '.',
-                    };
-                    print!("{}", c);
-                }
+    fn to_frame(&self) -> Frame {
+        Frame::from_grid(&self.base, |pos, tile| {
+            if *pos == self.robot_pos {
+                return Cell::new('@', Color::Red);
             }
-            println!("");
-        }
-        println!("");
+
+            match tile {
+                TileType::Box => Cell::new('O', Color::Yellow),
+                TileType::Wall => Cell::new('#', Color::Gray),
+                TileType::Floor => Cell::plain('.'),
+            }
+        })
     }
 
     fn from_input(input: &str) -> Map {
@@ -134,13 +128,12 @@ impl Map {
     }
 
     fn process_moves(&mut self, moves: Vec<char>) {
-        // println!("Initial state");
-        // self.display();
+        let mut viz = Visualizer::from_args(DAY);
+        viz.show(&self.to_frame(), "Initial state");
         for char_move in moves {
             let offset_pos = move2pos(char_move);
-            // println!("Move {}:", char_move);
             self.move_robot(&offset_pos);
-            // self.display();
+            viz.show(&self.to_frame(), format!("Move {char_move}"));
         }
     }
 }
@@ -1,5 +1,14 @@
-use core::panic;
 use std::usize;
+use std::{
+    fmt::Write as _,
+    fs::File,
+    io::{self, stdin, stdout, BufWriter, Write},
+};
+
+use advent_of_code::debug;
+use advent_of_code::parse::{self, ParseError, Pattern};
+use advent_of_code::template::runner::Answer;
+use advent_of_code::viz;
 
 advent_of_code::solution!(17);
 
@@ -16,31 +25,44 @@ struct Computer {
 }
 
 impl Computer {
-    fn from_input(input: &str) -> Computer {
-        assert_eq!(input.lines().count(), 5, "There should be five lines!");
-
-        let mut lines = input.lines();
-        let a_reg = Computer::parse_reg_def(lines.next().unwrap(), "Register A:");
-        let b_reg = Computer::parse_reg_def(lines.next().unwrap(), "Register B:");
-        let c_reg = Computer::parse_reg_def(lines.next().unwrap(), "Register C:");
-
-        lines.next();
+    fn from_input(input: &str) -> Result<Computer, ParseError> {
+        let lines: Vec<&str> = input.lines().collect();
+        if lines.len() != 5 {
+            return Err(ParseError::new(
+                lines.len().min(5),
+                1,
+                "there should be five lines",
+            ));
+        }
 
-        let std_in = Computer::parse_program_input(lines.next().unwrap());
-        assert_eq!(
-            std_in.len() % 2,
-            0,
-            "There should be an even number of instr-operands"
-        );
+        let a_reg = Computer::parse_reg_def(lines[0], "A").map_err(|e| e.at_line(1))?;
+        let b_reg = Computer::parse_reg_def(lines[1], "B").map_err(|e| e.at_line(2))?;
+        let c_reg = Computer::parse_reg_def(lines[2], "C").map_err(|e| e.at_line(3))?;
+
+        let std_in = Computer::parse_program_input(lines[4]).map_err(|e| e.at_line(5))?;
+        if std_in.len() % 2 != 0 {
+            return Err(ParseError::new(
+                5,
+                1,
+                "there should be an even number of instr-operands",
+            ));
+        }
+        if std_in.iter().any(|value| *value >= 8) {
+            return Err(ParseError::new(
+                5,
+                1,
+                "instructions and operands should be 3-bit numbers",
+            ));
+        }
 
-        Computer {
+        Ok(Computer {
             a_reg,
             b_reg,
             c_reg,
             pc: 0,
             std_in,
             std_out: Vec::new(),
-        }
+        })
     }
 
     fn program_len(&self) -> usize {
@@ -55,38 +77,28 @@ impl Computer {
         self.std_out = Vec::new();
     }
 
-    fn parse_reg_def(input: &str, prefix: &str) -> u64 {
-        input
-            .strip_prefix(prefix)
-            .unwrap()
-            .trim()
-            .parse::<u64>()
-            .unwrap()
+    fn parse_reg_def(input: &str, register: &str) -> Result<u64, ParseError> {
+        let values = parse::scan(&format!("Register {register}: {{}}"), input)?;
+        Ok(values[0])
     }
 
-    fn parse_program_input(input: &str) -> Vec<u8> {
-        input
-            .strip_prefix("Program:")
-            .unwrap()
-            .trim()
-            .split(',')
-            .map(|num_s| num_s.parse::<u8>().unwrap())
-            .collect()
+    fn parse_program_input(input: &str) -> Result<Vec<u8>, ParseError> {
+        // only checks the prefix, the numbers are extracted with their position in the line.
+        Pattern::new("Program: {}").captures(input)?;
+        parse::ints(input)
     }
 
-    fn process_operand(&self, operand: u8, is_combo: bool) -> u64 {
-        assert!(operand < 8, "Operand is not 3-bit: {}", operand);
-
-        match is_combo {
-            false => operand as u64,
-            true => match operand {
-                0..=3 => operand as u64,
-                4 => self.a_reg,
-                5 => self.b_reg,
-                6 => self.c_reg,
-                7 => panic!("7 is reserved and should never appear!"),
-                val => panic!("{} is not a valid 3-bit number", val),
-            },
+    fn process_operand(&self, operand: u8, is_combo: bool) -> Result<u64, String> {
+        match (is_combo, operand) {
+            (_, 8..) => Err(format!("operand {operand} is not a 3-bit number")),
+            (false, _) | (true, 0..=3) => Ok(operand as u64),
+            (true, 4) => Ok(self.a_reg),
+            (true, 5) => Ok(self.b_reg),
+            (true, 6) => Ok(self.c_reg),
+            (true, _) => Err(format!(
+                "combo operand 7 is reserved, at pc {}",
+                self.pc - 2
+            )),
         }
     }
 
@@ -94,50 +106,93 @@ impl Computer {
         join_u8_vec(&self.std_out)
     }
 
-    fn run_until_end(&mut self) {
+    fn run_until_end(&mut self) -> Result<(), String> {
         while self.pc < self.std_in.len() {
-            self.run_one_cycle();
+            self.run_one_cycle()?;
         }
+        Ok(())
     }
 
-    fn run_one_cycle(&mut self) {
+    fn run_one_cycle(&mut self) -> Result<(), String> {
         let cur_pc = self.pc;
+        let (instruction, operand) = self
+            .instruction_at(cur_pc)
+            .ok_or_else(|| format!("no valid instruction at pc {cur_pc}"))?;
         self.pc += 2;
 
-        let op_code = self.std_in[cur_pc];
-        let instruction = Instr::from_opcode(op_code);
-        let operand = self.std_in[cur_pc + 1];
+        self.execute_instruction(instruction, operand)
+    }
+
+    /// Runs the program like [`Computer::run_until_end`], stopping and tracing as configured.
+    fn run_with_debugger(&mut self, debugger: &mut Debugger) -> Result<(), String> {
+        let mut cycle = 0;
+        while self.pc < self.std_in.len() {
+            if debugger.should_stop(self) {
+                debugger.prompt(self);
+            }
+
+            let executed_pc = self.pc;
+            self.run_one_cycle()?;
+            cycle += 1;
+            debugger.trace(cycle, executed_pc, self);
+        }
+        Ok(())
+    }
 
-        // if instruction == Instr::JNZ {
-        //     println!("A: {}", self.a_reg);
-        //     println!("A8: {}", self.a_reg % 8);
-        // }
+    /// Returns the instruction at `pc` and its operand, if `pc` is inside the program and the
+    /// opcode is valid.
+    fn instruction_at(&self, pc: usize) -> Option<(Instr, u8)> {
+        let op_code = *self.std_in.get(pc)?;
+        let operand = *self.std_in.get(pc + 1)?;
+        Some((Instr::from_opcode(op_code)?, operand))
+    }
 
-        self.execute_instruction(instruction, operand);
-        // println!("{}{} => {:?}", op_code, operand, self);
+    fn registers(&self) -> String {
+        format!("A={} B={} C={}", self.a_reg, self.b_reg, self.c_reg)
+    }
+
+    /// Lists the program as mnemonics, one instruction per line, e.g. `02  2,4  bst A%8   -> B`.
+    fn disassemble(&self) -> String {
+        let mut listing = String::new();
+        for pc in (0..self.program_len()).step_by(2) {
+            let (instruction, operand) = self.instruction_at(pc).unwrap();
+            let _ = writeln!(
+                listing,
+                "{pc:02}  {},{operand}  {:<10} -> {}",
+                instruction.to_opcode(),
+                instruction.describe(operand),
+                instruction.destination()
+            );
+        }
+        listing
     }
 
-    fn execute_instruction(&mut self, instruction: Instr, operand: u8) {
+    fn execute_instruction(&mut self, instruction: Instr, operand: u8) -> Result<(), String> {
+        // shifting by 64 bits or more leaves nothing of A.
+        let shifted = |a_reg: u64, shift: u64| {
+            u32::try_from(shift)
+                .ok()
+                .and_then(|shift| a_reg.checked_shr(shift))
+                .unwrap_or(0)
+        };
+
         match instruction {
-            Instr::ADV => self.a_reg /= 2_u64.pow(self.process_operand(operand, true) as u32),
-            Instr::BXL => self.b_reg ^= self.process_operand(operand, false),
-            Instr::BST => self.b_reg = self.process_operand(operand, true) % 8,
+            Instr::ADV => self.a_reg = shifted(self.a_reg, self.process_operand(operand, true)?),
+            Instr::BXL => self.b_reg ^= self.process_operand(operand, false)?,
+            Instr::BST => self.b_reg = self.process_operand(operand, true)? % 8,
             Instr::JNZ => {
                 if self.a_reg != 0 {
-                    self.pc = self.process_operand(operand, false) as usize;
+                    self.pc = self.process_operand(operand, false)? as usize;
                 }
             }
             Instr::BXC => self.b_reg ^= self.c_reg,
             Instr::OUT => self
                 .std_out
-                .push((self.process_operand(operand, true) % 8) as u8),
-            Instr::BDV => {
-                self.b_reg = self.a_reg / 2_u64.pow(self.process_operand(operand, true) as u32)
-            }
-            Instr::CDV => {
-                self.c_reg = self.a_reg / 2_u64.pow(self.process_operand(operand, true) as u32)
-            }
+                .push((self.process_operand(operand, true)? % 8) as u8),
+            Instr::BDV => self.b_reg = shifted(self.a_reg, self.process_operand(operand, true)?),
+            Instr::CDV => self.c_reg = shifted(self.a_reg, self.process_operand(operand, true)?),
         }
+        Ok(())
     }
 }
 
@@ -146,7 +201,7 @@ fn join_u8_vec(vec: &Vec<u8>) -> String {
     str_nums.join(",")
 }
 
-#[derive(PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum Instr {
     ADV = 0,
     BXL = 1,
@@ -159,7 +214,7 @@ enum Instr {
 }
 
 impl Instr {
-    fn to_opcode(&self) -> u8 {
+    fn to_opcode(self) -> u8 {
         match self {
             Instr::ADV => 0,
             Instr::BXL => 1,
@@ -172,60 +227,281 @@ impl Instr {
         }
     }
 
-    fn from_opcode(code: u8) -> Instr {
-        assert!(code < 8, "OP_CODE should be 3-bit: {}", code);
+    fn from_opcode(code: u8) -> Option<Instr> {
         match code {
-            0 => Self::ADV,
-            1 => Self::BXL,
-            2 => Self::BST,
-            3 => Self::JNZ,
-            4 => Self::BXC,
-            5 => Self::OUT,
-            6 => Self::BDV,
-            7 => Self::CDV,
-            _ => panic!("Unknown OP_CODE: {}", code),
+            0 => Some(Self::ADV),
+            1 => Some(Self::BXL),
+            2 => Some(Self::BST),
+            3 => Some(Self::JNZ),
+            4 => Some(Self::BXC),
+            5 => Some(Self::OUT),
+            6 => Some(Self::BDV),
+            7 => Some(Self::CDV),
+            _ => None,
+        }
+    }
+
+    fn mnemonic(&self) -> &'static str {
+        match self {
+            Instr::ADV => "adv",
+            Instr::BXL => "bxl",
+            Instr::BST => "bst",
+            Instr::JNZ => "jnz",
+            Instr::BXC => "bxc",
+            Instr::OUT => "out",
+            Instr::BDV => "bdv",
+            Instr::CDV => "cdv",
         }
     }
+
+    /// Mnemonic and decoded operation, e.g. `adv A>>B` or `out B%8`.
+    fn describe(&self, operand: u8) -> String {
+        let combo = combo_name(operand);
+        let operation = match self {
+            Instr::ADV | Instr::BDV | Instr::CDV => format!("A>>{combo}"),
+            Instr::BXL => format!("B^{operand}"),
+            Instr::BST | Instr::OUT => format!("{combo}%8"),
+            Instr::JNZ => format!("{operand}"),
+            Instr::BXC => "B^C".to_string(),
+        };
+        format!("{} {operation}", self.mnemonic())
+    }
+
+    /// Where the result of the instruction goes.
+    fn destination(&self) -> &'static str {
+        match self {
+            Instr::ADV => "A",
+            Instr::BXL | Instr::BST | Instr::BXC | Instr::BDV => "B",
+            Instr::CDV => "C",
+            Instr::JNZ => "pc if A!=0",
+            Instr::OUT => "out",
+        }
+    }
+}
+
+/// Decodes a combo operand: literals 0 to 3 or a register, 7 is reserved.
+fn combo_name(operand: u8) -> String {
+    match operand {
+        0..=3 => operand.to_string(),
+        4 => "A".to_string(),
+        5 => "B".to_string(),
+        6 => "C".to_string(),
+        _ => "?".to_string(),
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Breakpoint {
+    Pc(usize),
+    /// Any `out` instruction.
+    Out,
 }
 
-fn solve_self_replication(computer: &mut Computer) -> u64 {
-    let mut a_reg = 0;
-    for i in 0..computer.program_len() {
-        a_reg = find_a_for_iteration(a_reg, i, computer).unwrap();
+impl Breakpoint {
+    fn parse(value: &str) -> Option<Breakpoint> {
+        match value {
+            "out" => Some(Breakpoint::Out),
+            pc => pc.parse().ok().map(Breakpoint::Pc),
+        }
+    }
+
+    fn is_hit(&self, computer: &Computer) -> bool {
+        match self {
+            Breakpoint::Pc(pc) => computer.pc == *pc,
+            Breakpoint::Out => computer
+                .instruction_at(computer.pc)
+                .is_some_and(|(instruction, _)| instruction == Instr::OUT),
+        }
     }
-    a_reg
 }
 
-fn find_a_for_iteration(last_a: u64, iteration: usize, computer: &mut Computer) -> Option<u64> {
-    let min = last_a * 8;
-    let max = min + 64; // 64 is kinda of a magic value, there is probably a way cleaner way to
-                        // find the max but this works for now.
+struct Debugger {
+    stepping: bool,
+    breakpoints: Vec<Breakpoint>,
+    trace_file: Option<BufWriter<File>>,
+}
+
+/// Debugging tools of part one, enabled with arguments of the binary (see [`debug::Options`]),
+/// e.g. `cargo solve 17 --debug-disassemble --debug-trace --debug-break 4 --debug-break out`.
+impl Debugger {
+    fn new(options: &debug::Options) -> Debugger {
+        let trace_file = if options.trace {
+            match create_trace_file() {
+                Ok(file) => Some(file),
+                Err(e) => {
+                    eprintln!("Failed to create trace file: {e}");
+                    None
+                }
+            }
+        } else {
+            None
+        };
+
+        let breakpoints = options
+            .breakpoints
+            .iter()
+            .filter_map(|value| {
+                let breakpoint = Breakpoint::parse(value);
+                if breakpoint.is_none() {
+                    eprintln!("Ignoring breakpoint `{value}`, expecting a pc or `out`.");
+                }
+                breakpoint
+            })
+            .collect();
+
+        Debugger {
+            stepping: options.step,
+            breakpoints,
+            trace_file,
+        }
+    }
+
+    fn should_stop(&self, computer: &Computer) -> bool {
+        self.stepping || self.breakpoints.iter().any(|b| b.is_hit(computer))
+    }
 
-    for number in min..max {
-        computer.reset();
-        computer.a_reg = number;
-        computer.run_until_end();
+    /// Shows the state of the computer and waits for a command:
+    /// <Enter> steps, `c` continues to the next breakpoint and `q` runs to the end.
+    fn prompt(&mut self, computer: &Computer) {
+        let (instruction, operand) = computer.instruction_at(computer.pc).unwrap();
+        println!(
+            "pc={:02} {:<10} {} out=[{}]",
+            computer.pc,
+            instruction.describe(operand),
+            computer.registers(),
+            computer.out()
+        );
+        print!("[Enter] step, (c)ontinue, (q)uit > ");
+        let _ = stdout().flush();
 
-        let sub_std_in = computer.std_in[computer.program_len() - iteration - 1..].to_vec();
-        if computer.std_out != sub_std_in {
+        let mut command = String::new();
+        // without a terminal (end of input), run to the end.
+        if !matches!(stdin().read_line(&mut command), Ok(read) if read > 0) {
+            command = "q".into();
+        }
+        match command.trim() {
+            "c" => self.stepping = false,
+            "q" => {
+                self.stepping = false;
+                self.breakpoints.clear();
+            }
+            _ => self.stepping = true,
+        }
+    }
+
+    fn trace(&mut self, cycle: usize, executed_pc: usize, computer: &Computer) {
+        let Some(file) = self.trace_file.as_mut() else {
+            return;
+        };
+
+        let (instruction, operand) = computer.instruction_at(executed_pc).unwrap();
+        let written = writeln!(
+            file,
+            "{cycle:>6}  pc={executed_pc:02}  {:<10} {} out=[{}]",
+            instruction.describe(operand),
+            computer.registers(),
+            computer.out()
+        );
+        if let Err(e) = written {
+            eprintln!("Failed to write trace: {e}");
+            self.trace_file = None;
+        }
+    }
+}
+
+fn create_trace_file() -> Result<BufWriter<File>, io::Error> {
+    let path = viz::output_dir(DAY)?.join("trace.txt");
+    println!("Writing execution trace to \"{}\"", path.display());
+    Ok(BufWriter::new(File::create(path)?))
+}
+
+/// Checks that the program is a single loop consuming one octal digit of A per iteration:
+/// it shifts A right by 3 once, outputs one value and jumps back to the start at the end.
+/// Each output then only depends on the digits of A that are still left.
+fn check_quine_structure(computer: &Computer) -> Result<(), String> {
+    let instructions: Vec<(Instr, u8)> = (0..computer.program_len())
+        .step_by(2)
+        .filter_map(|pc| computer.instruction_at(pc))
+        .collect();
+    let count = |instr: Instr| instructions.iter().filter(|(i, _)| *i == instr).count();
+
+    if instructions.last() != Some(&(Instr::JNZ, 0)) || count(Instr::JNZ) != 1 {
+        return Err("the program should end with its only jump, `jnz 0`".into());
+    }
+    if !instructions.contains(&(Instr::ADV, 3)) || count(Instr::ADV) != 1 {
+        return Err("A should be shifted right by 3 exactly once per loop, `adv 3`".into());
+    }
+    if count(Instr::OUT) != 1 {
+        return Err("the program should output exactly one value per loop".into());
+    }
+    Ok(())
+}
+
+/// Returns the sorted values of A for which the program outputs itself.
+///
+/// The last output only depends on the most significant octal digit of A, the one before on the
+/// two most significant digits, and so on. The digits are chosen from the most significant one,
+/// keeping those for which the output matches the end of the program.
+fn quine_values(computer: &mut Computer) -> Result<Vec<u64>, String> {
+    check_quine_structure(computer)?;
+
+    let mut values = vec![];
+    let mut stack = vec![(0_u64, 0_usize)];
+    while let Some((prefix, digits)) = stack.pop() {
+        if digits == computer.program_len() {
+            values.push(prefix);
             continue;
         }
 
-        return Some(number);
+        let expected_start = computer.program_len() - digits - 1;
+        for digit in 0..8 {
+            let a_reg = prefix * 8 + digit;
+            // a leading zero digit gives the same value as a shorter A.
+            if a_reg == 0 {
+                continue;
+            }
+
+            computer.reset();
+            computer.a_reg = a_reg;
+            computer.run_until_end()?;
+            if computer.std_out == computer.std_in[expected_start..] {
+                stack.push((a_reg, digits + 1));
+            }
+        }
     }
 
-    None
+    values.sort_unstable();
+    Ok(values)
 }
 
-pub fn part_one(input: &str) -> Option<String> {
-    let mut computer = Computer::from_input(input);
-    computer.run_until_end();
-    Some(computer.out())
+pub fn part_one(input: &str) -> Result<Answer<String>, ParseError> {
+    let mut computer = Computer::from_input(input)?;
+
+    let options = debug::Options::from_env();
+    if options.disassemble {
+        print!("{}", computer.disassemble());
+    }
+
+    let run = if options.needs_debugger() {
+        computer.run_with_debugger(&mut Debugger::new(&options))
+    } else {
+        computer.run_until_end()
+    };
+    Ok(match run {
+        Ok(()) => Answer::Solved(computer.out()),
+        Err(e) => Answer::Error(e),
+    })
 }
 
-pub fn part_two(input: &str) -> Option<u64> {
-    let mut computer = Computer::from_input(input);
-    Some(solve_self_replication(&mut computer))
+pub fn part_two(input: &str) -> Result<Answer<u64>, ParseError> {
+    let mut computer = Computer::from_input(input)?;
+    Ok(match quine_values(&mut computer) {
+        Ok(values) => match values.first() {
+            Some(a_reg) => Answer::Solved(*a_reg),
+            None => Answer::NotFound,
+        },
+        Err(e) => Answer::Error(format!("not a self-replicating program: {e}")),
+    })
 }
 
 #[cfg(test)]
@@ -237,7 +513,10 @@ mod tests {
         let result = part_one(&advent_of_code::template::read_file_part(
             "examples", DAY, 1,
         ));
-        assert_eq!(result, Some("4,6,3,5,6,3,5,2,1,0".to_string()));
+        assert_eq!(
+            result,
+            Ok(Answer::Solved("4,6,3,5,6,3,5,2,1,0".to_string()))
+        );
     }
 
     #[test]
@@ -245,6 +524,73 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file_part(
             "examples", DAY, 2,
         ));
-        assert_eq!(result, Some(117440));
+        assert_eq!(result, Ok(Answer::Solved(117440)));
+    }
+
+    fn computer(program: &str) -> Computer {
+        let input = format!("Register A: 729\nRegister B: 0\nRegister C: 0\n\nProgram: {program}");
+        Computer::from_input(&input).unwrap()
+    }
+
+    #[test]
+    fn disassembles_programs() {
+        assert_eq!(
+            computer("0,1,5,4,3,0").disassemble(),
+            "00  0,1  adv A>>1   -> A\n02  5,4  out A%8    -> out\n04  3,0  jnz 0      -> pc if A!=0\n"
+        );
+    }
+
+    #[test]
+    fn stops_at_breakpoints() {
+        let mut computer = computer("0,1,5,4,3,0");
+        let options = debug::Options {
+            breakpoints: vec!["out".into(), "9".into()],
+            ..debug::Options::default()
+        };
+        let debugger = Debugger::new(&options);
+        assert_eq!(
+            debugger.breakpoints,
+            vec![Breakpoint::Out, Breakpoint::Pc(9)]
+        );
+
+        while !debugger.should_stop(&computer) {
+            computer.run_one_cycle().unwrap();
+        }
+        assert_eq!((computer.pc, computer.a_reg), (2, 364));
+    }
+
+    #[test]
+    fn finds_sorted_quine_values() {
+        // the lowest octal digit of A is shifted out before the first output, so it is free.
+        assert_eq!(
+            quine_values(&mut computer("0,3,5,4,3,0")),
+            Ok((117440..=117447).collect())
+        );
+    }
+
+    #[test]
+    fn rejects_programs_without_quine_structure() {
+        let part_one_example = advent_of_code::template::read_file_part("examples", DAY, 1);
+        assert_eq!(
+            part_two(&part_one_example),
+            Ok(Answer::Error(
+                "not a self-replicating program: A should be shifted right by 3 exactly once per loop, `adv 3`".to_string()
+            ))
+        );
+
+        assert!(check_quine_structure(&computer("0,3,5,4,5,4,3,0")).is_err());
+        assert!(check_quine_structure(&computer("0,3,5,4,3,0,5,4")).is_err());
+    }
+
+    #[test]
+    fn reports_invalid_programs() {
+        let input = "Register A: 0\nRegister B: 0\nRegister C: 0\n\nProgram: 0,9";
+        assert!(Computer::from_input(input).is_err());
+
+        let mut computer = computer("0,1,5,7");
+        assert_eq!(
+            computer.run_until_end(),
+            Err("combo operand 7 is reserved, at pc 2".to_string())
+        );
     }
 }
@@ -1,6 +1,7 @@
-use core::panic;
 use std::usize;
 
+use advent_of_code::parse::{self, ParseError};
+use advent_of_code::template::runner::Answer;
 use advent_of_code::{idx2pos, pos2idx, Pos2D};
 
 advent_of_code::solution!(18);
@@ -84,28 +85,22 @@ impl Map {
     }
 }
 
-fn preprocess_input(input: &str) -> ((usize, usize, usize), String) {
-    let meta: Vec<&str> = input.lines().next().unwrap().split(",").collect();
+/// The width, the height and the number of bytes fallen in part one.
+type Meta = (usize, usize, usize);
 
-    let width = meta.get(0).unwrap().parse::<usize>().unwrap();
-    let height = meta.get(1).unwrap().parse::<usize>().unwrap();
-    let simulated_bytes = meta.get(2).unwrap().parse::<usize>().unwrap();
+fn preprocess_input(input: &str) -> Result<(Meta, Vec<Pos2D>), ParseError> {
+    let (meta_line, remain) = input.split_once("\n\n").unwrap_or((input, ""));
+    let meta: Vec<usize> = parse::scan("{},{},{}", meta_line)?;
 
-    let remain = input.lines().skip(2).collect::<Vec<&str>>().join("\n");
-    ((width, height, simulated_bytes), remain)
+    let bytes = parse::lines_with(remain, parse_coords).map_err(|e| e.offset_lines(2))?;
+    Ok(((meta[0], meta[1], meta[2]), bytes))
 }
 
-fn create_map(input: &str, width: usize, height: usize, simulated_bytes: usize) -> Map {
+fn create_map(bytes: &[Pos2D], width: usize, height: usize, simulated_bytes: usize) -> Map {
     let mut tiles = vec!['.'; width * height];
-    let obstacles: Vec<Pos2D> = input
-        .lines()
-        .take(simulated_bytes)
-        .map(|l| parse_coords(l))
-        .collect();
-
-    for obstacle in obstacles.iter() {
-        let pos = obstacle;
-        let idx = pos2idx(&pos, width, height).unwrap();
+
+    for obstacle in bytes.iter().take(simulated_bytes) {
+        let idx = pos2idx(obstacle, width, height).unwrap();
         tiles[idx] = '#';
     }
 
@@ -182,52 +177,47 @@ fn a_star(map: &Map) -> Result<Vec<Node>, ()> {
     Err(())
 }
 
-fn parse_coords(input: &str) -> Pos2D {
-    let (x, y) = input
-        .split_once(',')
-        .map(|(x, y)| (x.parse::<i32>().unwrap(), y.parse::<i32>().unwrap()))
-        .unwrap();
-
-    Pos2D::new(x, y)
+fn parse_coords(input: &str) -> Result<Pos2D, ParseError> {
+    let coords: Vec<i32> = parse::scan("{},{}", input)?;
+    Ok(Pos2D::new(coords[0], coords[1]))
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let (meta, input) = preprocess_input(input);
+pub fn part_one(input: &str) -> Result<Answer<u32>, ParseError> {
+    let (meta, bytes) = preprocess_input(input)?;
     let width = meta.0;
     let height = meta.1;
     let simulated_bytes = meta.2;
 
-    let map = create_map(&input, width, height, simulated_bytes);
+    let map = create_map(&bytes, width, height, simulated_bytes);
 
     if let Ok(shortest_path) = a_star(&map) {
         // start node does not count as a step
         let steps = shortest_path.len() - 1;
-        return Some(steps as u32);
+        return Ok(Answer::Solved(steps as u32));
     }
 
-    None
+    Ok(Answer::NotFound)
 }
 
-pub fn part_two(input: &str) -> Option<String> {
-    let (meta, input) = preprocess_input(input);
+pub fn part_two(input: &str) -> Result<Answer<String>, ParseError> {
+    let (meta, bytes) = preprocess_input(input)?;
     let width = meta.0;
     let height = meta.1;
 
-    let max_bytes = input.lines().count();
-    for i in 0..=max_bytes {
-        let map = create_map(&input, width, height, i);
-        match a_star(&map) {
-            Ok(shortest_path) => {
-                // map.display_path(&shortest_path);
-            }
-            _err => match input.lines().nth(i - 1).map(|l| parse_coords(l)) {
-                Some(pos) => return Some(format!("{},{}", pos.x, pos.y)),
-                None => panic!("parse_coords should always work here!"),
-            },
-        };
+    for i in 0..=bytes.len() {
+        let map = create_map(&bytes, width, height, i);
+        if a_star(&map).is_err() {
+            // without any fallen byte, no byte is to blame.
+            let Some(&pos) = i.checked_sub(1).and_then(|last| bytes.get(last)) else {
+                return Ok(Answer::Error(
+                    "there is no path even before any byte falls".to_string(),
+                ));
+            };
+            return Ok(Answer::Solved(format!("{},{}", pos.x, pos.y)));
+        }
     }
 
-    None
+    Ok(Answer::NotFound)
 }
 
 #[cfg(test)]
@@ -237,12 +227,12 @@ mod tests {
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(22));
+        assert_eq!(result, Ok(Answer::Solved(22)));
     }
 
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some("6,1".to_string()));
+        assert_eq!(result, Ok(Answer::Solved("6,1".to_string())));
     }
 }
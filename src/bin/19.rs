@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use advent_of_code::memoize::Memo;
 
 advent_of_code::solution!(19);
 
@@ -21,36 +21,26 @@ fn parse_input(input: &str) -> (Vec<&str>, Vec<&str>) {
 // - 3. Match design against pattern instead of matching pattern against design
 // - 4. Early invalidation of patterns: First check if pattern is contained within the design and
 // if not, remove it entirely from the list for that design
-fn can_prefix(design: &str, patterns: &Vec<&str>, cache: &mut HashMap<usize, ()>) -> bool {
+fn can_prefix(design: &str, patterns: &Vec<&str>, memo: &mut Memo<usize, bool>) -> bool {
     if design.is_empty() {
         return true;
     }
 
-    for pattern in patterns.iter() {
-        if let Some(stripped_design) = design.strip_prefix(pattern) {
-            let key = stripped_design.len();
-            if cache.get(&key).is_some() {
-                return false;
-            }
-
-            if can_prefix(stripped_design, &patterns, cache) {
-                return true;
-            }
-        }
-    }
-
-    if cache.get(&design.len()).is_none() {
-        cache.insert(design.len(), ());
-    }
-
-    false
+    // the rest of a design only depends on its length.
+    memo.get_or_compute(design.len(), |memo| {
+        patterns.iter().any(|pattern| {
+            design
+                .strip_prefix(pattern)
+                .is_some_and(|stripped_design| can_prefix(stripped_design, patterns, memo))
+        })
+    })
 }
 
 fn find_possible_designs<'a>(patterns: &Vec<&'a str>, designs: &Vec<&'a str>) -> Vec<&'a str> {
     let mut possible_designs = vec![];
     for &design in designs.iter() {
-        let mut cache: HashMap<usize, ()> = HashMap::new();
-        if can_prefix(design, patterns, &mut cache) {
+        let mut memo = Memo::new();
+        if can_prefix(design, patterns, &mut memo) {
             possible_designs.push(design);
         }
     }
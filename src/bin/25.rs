@@ -1,38 +1,23 @@
-use std::collections::HashMap;
-
-use itertools::Itertools;
+use advent_of_code::Map2D;
 
 advent_of_code::solution!(25);
 
-type HeightMap = HashMap<usize, i32>;
+type HeightMap = Vec<usize>;
 
 fn parse_input(input: &str) -> (Vec<HeightMap>, Vec<HeightMap>) {
     let mut locks = vec![];
     let mut keys = vec![];
 
-    for schematic in input.split("\n\n").collect::<Vec<_>>().iter() {
-        let mut height_map: HeightMap = HashMap::new();
-        assert_eq!(
-            7,
-            schematic.lines().count(),
-            "Schematic should have 7 rows!"
-        );
-
-        let mut is_key = false;
-        for (i, line) in schematic.lines().enumerate() {
-            assert_eq!(5, line.len(), "Schematic should have 5 columns!");
+    for schematic in input.split("\n\n").map(Map2D::from_chars) {
+        assert_eq!(7, schematic.height, "Schematic should have 7 rows!");
+        assert_eq!(5, schematic.width, "Schematic should have 5 columns!");
 
-            if i == 0 {
-                is_key = line.chars().all(|c| c == '.');
-            }
-
-            for (j, c) in line.chars().enumerate() {
-                if c == '#' {
-                    let height = height_map.get(&j).unwrap_or(&-1);
-                    height_map.insert(j, height + 1);
-                }
-            }
-        }
+        let is_key = schematic.row(0).all(|&c| c == '.');
+        let height_map = schematic
+            .columns()
+            // the full row of a lock or a key is not part of the height, an empty column is 0.
+            .map(|column| column.filter(|&&c| c == '#').count().saturating_sub(1))
+            .collect();
 
         if is_key {
             keys.push(height_map);
@@ -45,7 +30,7 @@ fn parse_input(input: &str) -> (Vec<HeightMap>, Vec<HeightMap>) {
 }
 
 fn is_fit(lock: &HeightMap, key: &HeightMap) -> bool {
-    (0..lock.len()).all(|i| lock.get(&i).unwrap() + key.get(&i).unwrap() <= 5)
+    lock.iter().zip(key).all(|(lock, key)| lock + key <= 5)
 }
 
 pub fn part_one(input: &str) -> Option<u32> {
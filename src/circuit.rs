@@ -0,0 +1,871 @@
+/// Boolean circuits of two-input logic gates, such as the wires of day 24.
+///
+/// Wire names are interned to [`WireId`]s and gates are evaluated in topological order, so the
+/// same circuit can be run on any input. Circuits adding the `x` and `y` buses into the `z` bus
+/// can be checked against the structure of a ripple-carry adder, and repaired by swapping the
+/// outputs of gates. Circuits can be exported as Graphviz DOT and Mermaid diagrams.
+use std::{
+    collections::{BTreeMap, HashMap, VecDeque},
+    error::Error,
+    fmt::{Display, Write},
+    str::FromStr,
+};
+
+use crate::parse::{self, ParseError, Pattern};
+
+pub type WireId = usize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GateKind {
+    And,
+    Or,
+    Xor,
+}
+
+impl GateKind {
+    pub fn apply(self, a: bool, b: bool) -> bool {
+        match self {
+            GateKind::And => a & b,
+            GateKind::Or => a | b,
+            GateKind::Xor => a ^ b,
+        }
+    }
+}
+
+impl FromStr for GateKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "AND" => Ok(GateKind::And),
+            "OR" => Ok(GateKind::Or),
+            "XOR" => Ok(GateKind::Xor),
+            _ => Err(format!("unknown gate `{s}`, expecting AND, OR or XOR")),
+        }
+    }
+}
+
+impl Display for GateKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            GateKind::And => "AND",
+            GateKind::Or => "OR",
+            GateKind::Xor => "XOR",
+        };
+        write!(f, "{name}")
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Gate {
+    pub kind: GateKind,
+    pub inputs: [WireId; 2],
+    pub output: WireId,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CircuitError {
+    /// The wires of a loop of gates.
+    Cycle(Vec<String>),
+    /// A wire is the output of several gates.
+    MultipleDrivers(String),
+    /// The `x` and `y` buses should have the same width and the `z` bus one more bit.
+    NotAnAdder,
+}
+
+impl Display for CircuitError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CircuitError::Cycle(wires) => {
+                write!(f, "the gates loop through {}", wires.join(" -> "))
+            }
+            CircuitError::MultipleDrivers(wire) => {
+                write!(f, "wire `{wire}` is the output of several gates")
+            }
+            CircuitError::NotAnAdder => {
+                write!(f, "the circuit does not have the buses of an adder")
+            }
+        }
+    }
+}
+
+impl Error for CircuitError {}
+
+/// A gate that does not fit the ripple-carry adder structure.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Anomaly {
+    /// Index of the gate in [`Circuit::gates`].
+    pub gate: usize,
+    pub reason: &'static str,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Circuit {
+    names: Vec<String>,
+    ids: HashMap<String, WireId>,
+    gates: Vec<Gate>,
+    /// Values given in the input, by wire.
+    initial: Vec<Option<bool>>,
+}
+
+impl Circuit {
+    /// Parses the initial values (`x00: 1`), a blank line, then the gates (`x00 AND y00 -> z00`).
+    pub fn parse(input: &str) -> Result<Circuit, ParseError> {
+        let blocks = parse::blocks(input);
+        let [values, gates] = blocks.as_slice() else {
+            return Err(ParseError::new(
+                1,
+                1,
+                "expected the wire values and the gates, separated by a blank line",
+            ));
+        };
+
+        let mut circuit = Circuit::default();
+        for (idx, (name, value)) in parse::key_values(values.text)
+            .map_err(|e| e.offset_lines(values.line - 1))?
+            .into_iter()
+            .enumerate()
+        {
+            let value = match value {
+                "0" => false,
+                "1" => true,
+                _ => {
+                    return Err(ParseError::new(
+                        values.line + idx,
+                        name.len() + 3,
+                        format!("expected 0 or 1, got `{value}`"),
+                    ))
+                }
+            };
+            let wire = circuit.wire(name);
+            circuit.initial[wire] = Some(value);
+        }
+
+        let pattern = Pattern::new("{} {} {} -> {}");
+        for (idx, line) in gates.text.lines().enumerate() {
+            let line_nb = gates.line + idx;
+            let captures = pattern.captures(line).map_err(|e| e.at_line(line_nb))?;
+            let kind = captures[1]
+                .parse()
+                .map_err(|e| ParseError::new(line_nb, captures[0].len() + 2, e))?;
+
+            let inputs = [circuit.wire(captures[0]), circuit.wire(captures[2])];
+            let output = circuit.wire(captures[3]);
+            circuit.gates.push(Gate {
+                kind,
+                inputs,
+                output,
+            });
+        }
+
+        Ok(circuit)
+    }
+
+    /// Returns the id of a wire, creating it if needed.
+    pub fn wire(&mut self, name: &str) -> WireId {
+        if let Some(&id) = self.ids.get(name) {
+            return id;
+        }
+
+        let id = self.names.len();
+        self.names.push(name.to_string());
+        self.ids.insert(name.to_string(), id);
+        self.initial.push(None);
+        id
+    }
+
+    pub fn id(&self, name: &str) -> Option<WireId> {
+        self.ids.get(name).copied()
+    }
+
+    pub fn name(&self, wire: WireId) -> &str {
+        &self.names[wire]
+    }
+
+    pub fn wire_count(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn gates(&self) -> &[Gate] {
+        &self.gates
+    }
+
+    /// Returns the index of the gate driving each wire, [`None`] for the inputs of the circuit.
+    pub fn drivers(&self) -> Result<Vec<Option<usize>>, CircuitError> {
+        let mut drivers = vec![None; self.wire_count()];
+        for (idx, gate) in self.gates.iter().enumerate() {
+            if drivers[gate.output].replace(idx).is_some() {
+                return Err(CircuitError::MultipleDrivers(
+                    self.name(gate.output).to_string(),
+                ));
+            }
+        }
+        Ok(drivers)
+    }
+
+    /// Returns the indices of the gates reading each wire.
+    pub fn consumers(&self) -> Vec<Vec<usize>> {
+        let mut consumers = vec![vec![]; self.wire_count()];
+        for (idx, gate) in self.gates.iter().enumerate() {
+            for input in gate.inputs {
+                consumers[input].push(idx);
+            }
+        }
+        consumers
+    }
+
+    /// Sorts the gates so that each one comes after the gates driving its inputs.
+    pub fn topological_order(&self) -> Result<Vec<usize>, CircuitError> {
+        let drivers = self.drivers()?;
+        let consumers = self.consumers();
+
+        let mut pending: Vec<usize> = self
+            .gates
+            .iter()
+            .map(|gate| {
+                gate.inputs
+                    .iter()
+                    .filter(|i| drivers[**i].is_some())
+                    .count()
+            })
+            .collect();
+        let mut queue: VecDeque<usize> = (0..self.gates.len())
+            .filter(|idx| pending[*idx] == 0)
+            .collect();
+
+        let mut order = Vec::with_capacity(self.gates.len());
+        while let Some(idx) = queue.pop_front() {
+            order.push(idx);
+            for &next in &consumers[self.gates[idx].output] {
+                pending[next] -= 1;
+                if pending[next] == 0 {
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        if order.len() < self.gates.len() {
+            return Err(CircuitError::Cycle(self.find_cycle(&drivers, &pending)));
+        }
+        Ok(order)
+    }
+
+    /// Walks back from a gate left out of the topological order until a wire repeats.
+    fn find_cycle(&self, drivers: &[Option<usize>], pending: &[usize]) -> Vec<String> {
+        let is_stuck = |gate: usize| pending[gate] > 0;
+        let mut gate = (0..self.gates.len()).find(|idx| is_stuck(*idx)).unwrap();
+        let mut visited: Vec<WireId> = vec![];
+
+        loop {
+            let wire = self.gates[gate].output;
+            if let Some(start) = visited.iter().position(|w| *w == wire) {
+                let mut cycle: Vec<String> = visited[start..]
+                    .iter()
+                    .rev()
+                    .map(|w| self.name(*w).to_string())
+                    .collect();
+                cycle.push(cycle[0].clone());
+                return cycle;
+            }
+            visited.push(wire);
+
+            // a stuck gate always has a stuck driver, otherwise it would have been processed.
+            gate = self.gates[gate]
+                .inputs
+                .iter()
+                .filter_map(|input| drivers[*input])
+                .find(|driver| is_stuck(*driver))
+                .unwrap();
+        }
+    }
+
+    fn evaluate_in_order(&self, order: &[usize], values: &mut [Option<bool>]) {
+        for &idx in order {
+            let gate = &self.gates[idx];
+            if let [Some(a), Some(b)] = gate.inputs.map(|input| values[input]) {
+                values[gate.output] = Some(gate.kind.apply(a, b));
+            }
+        }
+    }
+
+    /// Evaluates the circuit with the values of the input. Wires that depend on a wire without
+    /// value stay [`None`].
+    pub fn simulate(&self) -> Result<Vec<Option<bool>>, CircuitError> {
+        let mut values = self.initial.clone();
+        self.evaluate_in_order(&self.topological_order()?, &mut values);
+        Ok(values)
+    }
+
+    /// Returns the wires of a bus (e.g. `z00`, `z01`...), least significant bit first.
+    pub fn bus(&self, prefix: char) -> Vec<WireId> {
+        let mut bits: Vec<(usize, WireId)> = self
+            .names
+            .iter()
+            .enumerate()
+            .filter_map(|(id, name)| Some((bus_bit(name, prefix)?, id)))
+            .collect();
+        bits.sort_unstable();
+        bits.into_iter().map(|(_, id)| id).collect()
+    }
+
+    /// Reads a bus as a number, [`None`] if a bit has no value.
+    pub fn bus_value(&self, values: &[Option<bool>], prefix: char) -> Option<u64> {
+        self.bus(prefix)
+            .iter()
+            .rev()
+            .try_fold(0, |acc, wire| Some(acc << 1 | u64::from(values[*wire]?)))
+    }
+
+    /// Swaps the output wires of the gates driving `a` and `b`.
+    pub fn swap_outputs(&mut self, a: WireId, b: WireId) {
+        for gate in &mut self.gates {
+            if gate.output == a {
+                gate.output = b;
+            } else if gate.output == b {
+                gate.output = a;
+            }
+        }
+    }
+
+    /// Returns the width of the numbers added by the circuit.
+    pub fn adder_width(&self) -> Result<usize, CircuitError> {
+        let width = self.bus('x').len();
+        if width == 0 || width >= 64 || self.bus('y').len() != width {
+            return Err(CircuitError::NotAnAdder);
+        }
+        if self.bus('z').len() != width + 1 {
+            return Err(CircuitError::NotAnAdder);
+        }
+        Ok(width)
+    }
+
+    /// Computes `x + y` with the circuit, [`None`] if some output bit has no value.
+    pub fn add(&self, x: u64, y: u64) -> Result<Option<u64>, CircuitError> {
+        let order = self.topological_order()?;
+        Ok(self.add_in_order(&order, x, y))
+    }
+
+    fn add_in_order(&self, order: &[usize], x: u64, y: u64) -> Option<u64> {
+        let mut values = vec![None; self.wire_count()];
+        for (prefix, number) in [('x', x), ('y', y)] {
+            for (bit, wire) in self.bus(prefix).into_iter().enumerate() {
+                values[wire] = Some(number >> bit & 1 == 1);
+            }
+        }
+
+        self.evaluate_in_order(order, &mut values);
+        self.bus_value(&values, 'z')
+    }
+
+    /// Checks that the circuit adds numbers, on each bit alone and on long carry chains.
+    /// Returns `false` as well when the gates loop.
+    pub fn is_adder(&self) -> Result<bool, CircuitError> {
+        let width = self.adder_width()?;
+        let Ok(order) = self.topological_order() else {
+            return Ok(false);
+        };
+
+        let mask = (1 << width) - 1;
+        let mut cases = vec![
+            (mask, 1),
+            (1, mask),
+            (mask, mask),
+            (0x5555_5555_5555_5555 & mask, 0xaaaa_aaaa_aaaa_aaaa & mask),
+        ];
+        for bit in 0..width {
+            cases.extend([(1 << bit, 0), (0, 1 << bit), (1 << bit, 1 << bit)]);
+        }
+
+        Ok(cases
+            .into_iter()
+            .all(|(x, y)| self.add_in_order(&order, x, y) == Some(x + y)))
+    }
+
+    /// Returns the gates that do not fit a ripple-carry adder, where each bit `k` is a full adder:
+    /// `x XOR y` and the carry-in go through a XOR to `z`, the carry-out is the OR of `x AND y`
+    /// and of the AND of the half sum and the carry-in. The last `z` bit is the last carry.
+    pub fn adder_anomalies(&self) -> Result<Vec<Anomaly>, CircuitError> {
+        let width = self.adder_width()?;
+        let consumers = self.consumers();
+        let feeds = |wire: WireId, kind: GateKind| {
+            consumers[wire]
+                .iter()
+                .any(|idx| self.gates[*idx].kind == kind)
+        };
+
+        let anomalies = self
+            .gates
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, gate)| {
+                let input_bits = gate.inputs.map(|input| {
+                    let name = self.name(input);
+                    bus_bit(name, 'x').or_else(|| bus_bit(name, 'y'))
+                });
+                let reads_inputs = input_bits.iter().all(Option::is_some);
+                let is_first_bit = input_bits == [Some(0), Some(0)];
+                let output_bit = bus_bit(self.name(gate.output), 'z');
+
+                let reason = match (gate.kind, output_bit) {
+                    (GateKind::Or, Some(bit)) if bit == width => None,
+                    (_, Some(bit)) if bit == width => Some("the last output bit should be an OR"),
+                    (GateKind::Xor, Some(0)) if is_first_bit => None,
+                    (_, Some(0)) => Some("the first output bit should be the XOR of x00 and y00"),
+                    (GateKind::Xor, Some(_)) if reads_inputs => {
+                        Some("the half sum of a bit should not be an output")
+                    }
+                    (GateKind::Xor, Some(_)) => None,
+                    (_, Some(_)) => Some("output bits should come from a XOR"),
+                    (GateKind::Xor, None) if !reads_inputs => {
+                        Some("a XOR of the carry-in should output a bit")
+                    }
+                    (GateKind::Xor, None) if !feeds(gate.output, GateKind::Xor) => {
+                        Some("the half sum of a bit should feed the XOR of its output")
+                    }
+                    (GateKind::And, None) if !is_first_bit && !feeds(gate.output, GateKind::Or) => {
+                        Some("an AND should feed the carry OR")
+                    }
+                    (GateKind::And, None) if is_first_bit && !feeds(gate.output, GateKind::Xor) => {
+                        Some("the first carry should feed the XOR of the next bit")
+                    }
+                    (GateKind::Or, None) if !feeds(gate.output, GateKind::Xor) => {
+                        Some("a carry should feed the XOR of the next bit")
+                    }
+                    _ => None,
+                };
+
+                reason.map(|reason| Anomaly { gate: idx, reason })
+            })
+            .collect();
+
+        Ok(anomalies)
+    }
+
+    /// Finds the fewest swaps of gate outputs that make the circuit add its inputs.
+    /// Only the outputs of the gates reported by [`Circuit::adder_anomalies`] are swapped.
+    /// Returns [`None`] if no combination of swaps works.
+    pub fn repair_adder(&self) -> Result<Option<Vec<(WireId, WireId)>>, CircuitError> {
+        let mut candidates: Vec<WireId> = self
+            .adder_anomalies()?
+            .iter()
+            .map(|anomaly| self.gates[anomaly.gate].output)
+            .collect();
+        candidates.sort_unstable();
+        candidates.dedup();
+
+        for swap_count in 0..=candidates.len() / 2 {
+            let mut swaps = vec![];
+            if self.search_swaps(
+                &candidates,
+                &mut vec![false; candidates.len()],
+                &mut swaps,
+                0,
+                swap_count,
+            )? {
+                return Ok(Some(swaps));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Tries every set of `left` more swaps of unused candidates, from `start` on.
+    fn search_swaps(
+        &self,
+        candidates: &[WireId],
+        used: &mut [bool],
+        swaps: &mut Vec<(WireId, WireId)>,
+        start: usize,
+        left: usize,
+    ) -> Result<bool, CircuitError> {
+        if left == 0 {
+            let mut repaired = self.clone();
+            for (a, b) in swaps.iter() {
+                repaired.swap_outputs(*a, *b);
+            }
+            return repaired.is_adder();
+        }
+
+        for first in start..candidates.len() {
+            if used[first] {
+                continue;
+            }
+            used[first] = true;
+
+            for second in first + 1..candidates.len() {
+                if used[second] {
+                    continue;
+                }
+                used[second] = true;
+                swaps.push((candidates[first], candidates[second]));
+
+                if self.search_swaps(candidates, used, swaps, first + 1, left - 1)? {
+                    return Ok(true);
+                }
+
+                swaps.pop();
+                used[second] = false;
+            }
+
+            used[first] = false;
+        }
+
+        Ok(false)
+    }
+}
+
+/// Fill colors of the gates in diagrams.
+fn gate_color(kind: GateKind) -> &'static str {
+    match kind {
+        GateKind::And => "#8ecae6",
+        GateKind::Or => "#90be6d",
+        GateKind::Xor => "#f4a261",
+    }
+}
+
+const BROKEN_COLOR: &str = "#d00000";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Node {
+    Wire(WireId),
+    Gate(usize),
+}
+
+/// The layout shared by the diagram formats: gates are nodes and wires are edges between them.
+/// Wires of the buses and wires without driver or reader are nodes as well. Nodes are grouped
+/// by the bit they compute.
+struct Diagram<'c> {
+    circuit: &'c Circuit,
+    groups: BTreeMap<Option<usize>, Vec<Node>>,
+    edges: Vec<(Node, Node, Option<&'c str>)>,
+    broken: HashMap<usize, &'static str>,
+}
+
+impl<'c> Diagram<'c> {
+    fn new(circuit: &'c Circuit) -> Result<Diagram<'c>, CircuitError> {
+        let drivers = circuit.drivers()?;
+        let consumers = circuit.consumers();
+        let broken = circuit
+            .adder_anomalies()
+            .map(|anomalies| anomalies.iter().map(|a| (a.gate, a.reason)).collect())
+            .unwrap_or_default();
+
+        let bus_wire_bit = |name: &str| ['x', 'y', 'z'].iter().find_map(|p| bus_bit(name, *p));
+        let is_wire_node = |wire: WireId| {
+            drivers[wire].is_none()
+                || consumers[wire].is_empty()
+                || bus_wire_bit(circuit.name(wire)).is_some()
+        };
+
+        // a gate belongs to the most significant bit of its inputs, or to its `z` output.
+        let mut wire_bits: Vec<Option<usize>> = circuit
+            .names
+            .iter()
+            .map(|name| bus_wire_bit(name))
+            .collect();
+        let mut gate_bits = vec![None; circuit.gates.len()];
+        for idx in circuit.topological_order().unwrap_or_default() {
+            let gate = &circuit.gates[idx];
+            let input_bit = gate.inputs.iter().filter_map(|i| wire_bits[*i]).max();
+            gate_bits[idx] = wire_bits[gate.output].or(input_bit);
+            wire_bits[gate.output] = gate_bits[idx];
+        }
+
+        let mut groups: BTreeMap<Option<usize>, Vec<Node>> = BTreeMap::new();
+        for wire in (0..circuit.wire_count()).filter(|wire| is_wire_node(*wire)) {
+            groups
+                .entry(wire_bits[wire])
+                .or_default()
+                .push(Node::Wire(wire));
+        }
+        for (idx, bit) in gate_bits.into_iter().enumerate() {
+            groups.entry(bit).or_default().push(Node::Gate(idx));
+        }
+
+        let mut edges = vec![];
+        for (idx, gate) in circuit.gates.iter().enumerate() {
+            for input in gate.inputs {
+                match drivers[input] {
+                    Some(driver) if !is_wire_node(input) => edges.push((
+                        Node::Gate(driver),
+                        Node::Gate(idx),
+                        Some(circuit.name(input)),
+                    )),
+                    _ => edges.push((Node::Wire(input), Node::Gate(idx), None)),
+                }
+            }
+            if is_wire_node(gate.output) {
+                edges.push((Node::Gate(idx), Node::Wire(gate.output), None));
+            }
+        }
+
+        Ok(Diagram {
+            circuit,
+            groups,
+            edges,
+            broken,
+        })
+    }
+
+    fn node_id(&self, node: Node) -> String {
+        match node {
+            // prefixed, wire names could be keywords of the formats.
+            Node::Wire(wire) => format!("w_{}", self.circuit.name(wire)),
+            Node::Gate(idx) => format!("g{idx}"),
+        }
+    }
+
+    fn group_label(bit: Option<usize>) -> String {
+        bit.map_or_else(|| "unassigned".to_string(), |bit| format!("bit {bit}"))
+    }
+
+    fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph circuit {\n    rankdir=LR;\n");
+        dot.push_str("    node [style=filled, fontname=\"monospace\"];\n");
+
+        for (bit, nodes) in &self.groups {
+            let indent = if bit.is_some() { "        " } else { "    " };
+            if let Some(bit) = bit {
+                let _ = writeln!(dot, "    subgraph cluster_bit_{bit:02} {{");
+                let _ = writeln!(
+                    dot,
+                    "        label=\"{}\";",
+                    Diagram::group_label(Some(*bit))
+                );
+            }
+
+            for node in nodes {
+                let id = self.node_id(*node);
+                let attributes = match *node {
+                    Node::Wire(wire) => format!(
+                        "label=\"{}\", shape=ellipse, fillcolor=\"#ffffff\"",
+                        self.circuit.name(wire)
+                    ),
+                    Node::Gate(idx) => {
+                        let kind = self.circuit.gates[idx].kind;
+                        let mut attributes = format!(
+                            "label=\"{kind}\", shape=box, fillcolor=\"{}\"",
+                            gate_color(kind)
+                        );
+                        if let Some(reason) = self.broken.get(&idx) {
+                            let _ = write!(
+                                attributes,
+                                ", color=\"{BROKEN_COLOR}\", penwidth=4, tooltip=\"{reason}\""
+                            );
+                        }
+                        attributes
+                    }
+                };
+                let _ = writeln!(dot, "{indent}{id} [{attributes}];");
+            }
+
+            if bit.is_some() {
+                dot.push_str("    }\n");
+            }
+        }
+
+        for (from, to, label) in &self.edges {
+            let (from, to) = (self.node_id(*from), self.node_id(*to));
+            match label {
+                Some(label) => {
+                    let _ = writeln!(dot, "    {from} -> {to} [label=\"{label}\"];");
+                }
+                None => {
+                    let _ = writeln!(dot, "    {from} -> {to};");
+                }
+            }
+        }
+
+        dot.push_str("}\n");
+        dot
+    }
+
+    fn to_mermaid(&self) -> String {
+        let mut mermaid = String::from("flowchart LR\n");
+
+        for (bit, nodes) in &self.groups {
+            let _ = writeln!(
+                mermaid,
+                "    subgraph {} [\"{}\"]",
+                bit.map_or_else(|| "unassigned".to_string(), |bit| format!("bit_{bit:02}")),
+                Diagram::group_label(*bit)
+            );
+            for node in nodes {
+                let id = self.node_id(*node);
+                let _ = match *node {
+                    Node::Wire(wire) => {
+                        writeln!(mermaid, "        {id}([\"{}\"])", self.circuit.name(wire))
+                    }
+                    Node::Gate(idx) => {
+                        writeln!(
+                            mermaid,
+                            "        {id}[\"{}\"]",
+                            self.circuit.gates[idx].kind
+                        )
+                    }
+                };
+            }
+            mermaid.push_str("    end\n");
+        }
+
+        for (from, to, label) in &self.edges {
+            let (from, to) = (self.node_id(*from), self.node_id(*to));
+            let _ = match label {
+                Some(label) => writeln!(mermaid, "    {from} -->|{label}| {to}"),
+                None => writeln!(mermaid, "    {from} --> {to}"),
+            };
+        }
+
+        for kind in [GateKind::And, GateKind::Or, GateKind::Xor] {
+            let class = kind.to_string().to_lowercase();
+            let _ = writeln!(mermaid, "    classDef {class} fill:{}", gate_color(kind));
+            let gates: Vec<String> = (0..self.circuit.gates.len())
+                .filter(|idx| self.circuit.gates[*idx].kind == kind)
+                .map(|idx| self.node_id(Node::Gate(idx)))
+                .collect();
+            if !gates.is_empty() {
+                let _ = writeln!(mermaid, "    class {} {class}", gates.join(","));
+            }
+        }
+
+        let _ = writeln!(
+            mermaid,
+            "    classDef broken stroke:{BROKEN_COLOR},stroke-width:4px"
+        );
+        let mut broken: Vec<usize> = self.broken.keys().copied().collect();
+        broken.sort_unstable();
+        for idx in broken {
+            let _ = writeln!(
+                mermaid,
+                "    class {} broken",
+                self.node_id(Node::Gate(idx))
+            );
+        }
+
+        mermaid
+    }
+}
+
+impl Circuit {
+    /// Renders the gates as a Graphviz DOT graph, colored by type and grouped by bit.
+    /// Gates that break the adder structure are outlined in red, their tooltip gives the reason.
+    pub fn to_dot(&self) -> Result<String, CircuitError> {
+        Ok(Diagram::new(self)?.to_dot())
+    }
+
+    /// Renders the gates as a Mermaid flowchart, with the same layout as [`Circuit::to_dot`].
+    pub fn to_mermaid(&self) -> Result<String, CircuitError> {
+        Ok(Diagram::new(self)?.to_mermaid())
+    }
+}
+
+/// Returns the bit of a bus wire, e.g. `3` for `z03` with the `z` prefix.
+pub fn bus_bit(name: &str, prefix: char) -> Option<usize> {
+    let digits = name.strip_prefix(prefix)?;
+    if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    digits.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns the gates of a ripple-carry adder of `width` bits, with all inputs at 0.
+    fn adder(width: usize) -> String {
+        let mut values = String::new();
+        let mut gates = String::new();
+        for bit in 0..width {
+            values.push_str(&format!("x{bit:02}: 0\ny{bit:02}: 0\n"));
+        }
+
+        gates.push_str("x00 XOR y00 -> z00\nx00 AND y00 -> c00\n");
+        for bit in 1..width {
+            let carry_in = format!("c{:02}", bit - 1);
+            let carry_out = if bit == width - 1 {
+                format!("z{width:02}")
+            } else {
+                format!("c{bit:02}")
+            };
+            gates.push_str(&format!(
+                "x{bit:02} XOR y{bit:02} -> s{bit:02}\n\
+                 s{bit:02} XOR {carry_in} -> z{bit:02}\n\
+                 x{bit:02} AND y{bit:02} -> a{bit:02}\n\
+                 s{bit:02} AND {carry_in} -> b{bit:02}\n\
+                 a{bit:02} OR b{bit:02} -> {carry_out}\n"
+            ));
+        }
+
+        format!("{values}\n{gates}")
+    }
+
+    #[test]
+    fn evaluates_in_topological_order() {
+        let circuit =
+            Circuit::parse("x00: 1\ny00: 1\n\ntmp OR y00 -> z00\nx00 AND y00 -> tmp\n").unwrap();
+        let values = circuit.simulate().unwrap();
+        assert_eq!(circuit.bus_value(&values, 'z'), Some(1));
+        assert_eq!(circuit.topological_order().unwrap(), vec![1, 0]);
+
+        let looping = Circuit::parse("x00: 1\n\nx00 AND b -> a\na OR x00 -> b\n").unwrap();
+        assert_eq!(
+            looping.topological_order(),
+            Err(CircuitError::Cycle(vec![
+                "b".into(),
+                "a".into(),
+                "b".into()
+            ]))
+        );
+
+        assert_eq!(
+            Circuit::parse("x00: 1\n\nx00 NAND x00 -> z00").unwrap_err(),
+            ParseError::new(3, 5, "unknown gate `NAND`, expecting AND, OR or XOR")
+        );
+    }
+
+    #[test]
+    fn checks_and_repairs_adders() {
+        let mut circuit = Circuit::parse(&adder(6)).unwrap();
+        assert_eq!(circuit.add(45, 27), Ok(Some(72)));
+        assert_eq!(circuit.is_adder(), Ok(true));
+        assert_eq!(circuit.adder_anomalies(), Ok(vec![]));
+
+        let (z03, b03) = (circuit.id("z03").unwrap(), circuit.id("b03").unwrap());
+        let (s04, a04) = (circuit.id("s04").unwrap(), circuit.id("a04").unwrap());
+        circuit.swap_outputs(z03, b03);
+        circuit.swap_outputs(s04, a04);
+        assert_eq!(circuit.is_adder(), Ok(false));
+        assert!(!circuit.adder_anomalies().unwrap().is_empty());
+
+        let mut swapped: Vec<&str> = circuit
+            .repair_adder()
+            .unwrap()
+            .unwrap()
+            .iter()
+            .flat_map(|(a, b)| [circuit.name(*a), circuit.name(*b)])
+            .collect();
+        swapped.sort_unstable();
+        assert_eq!(swapped, vec!["a04", "b03", "s04", "z03"]);
+    }
+
+    #[test]
+    fn highlights_broken_gates_in_diagrams() {
+        let mut circuit = Circuit::parse(&adder(3)).unwrap();
+        let dot = circuit.to_dot().unwrap();
+        assert!(dot.contains("subgraph cluster_bit_02 {"));
+        assert!(dot.contains(
+            "    g2 -> g3 [label=\"s01\"];\n    g1 -> g3 [label=\"c00\"];\n    g3 -> w_z01;\n"
+        ));
+        assert!(!dot.contains("penwidth"));
+
+        let (z01, b01) = (circuit.id("z01").unwrap(), circuit.id("b01").unwrap());
+        circuit.swap_outputs(z01, b01);
+        assert!(circuit.to_dot().unwrap().contains("penwidth=4"));
+
+        let mermaid = circuit.to_mermaid().unwrap();
+        assert!(mermaid.starts_with("flowchart LR\n    subgraph bit_00 [\"bit 0\"]\n"));
+        assert!(mermaid.contains("w_z01([\"z01\"])"));
+        assert!(mermaid.contains("    g5 --> w_z01\n"));
+        assert!(mermaid.ends_with("    class g3 broken\n    class g5 broken\n"));
+    }
+}
@@ -0,0 +1,153 @@
+/// Cycle detection for simulations that eventually repeat a state.
+///
+/// [`find_cycle`] and [`advance`] step a single state in place and only remember a key per step,
+/// so large states are never cloned. [`brent`] only keeps two states at a time, for sequences of
+/// small values that are cheap to clone and compare.
+use std::{collections::HashMap, hash::Hash};
+
+/// Where a sequence of states starts repeating.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    /// The first step whose state is repeated.
+    pub start: usize,
+    /// The number of steps before a state repeats.
+    pub length: usize,
+}
+
+impl Cycle {
+    /// The first step with the same state as step `n`.
+    pub fn reduce(&self, n: usize) -> usize {
+        if n < self.start {
+            n
+        } else {
+            self.start + (n - self.start) % self.length
+        }
+    }
+}
+
+/// Steps `state` until the key of a state repeats, and leaves it at step `start + length`.
+/// Loops forever if the states never repeat.
+pub fn find_cycle<S, K: Eq + Hash>(
+    state: &mut S,
+    mut step: impl FnMut(&mut S),
+    key: impl Fn(&S) -> K,
+) -> Cycle {
+    let mut seen = HashMap::new();
+    for idx in 0.. {
+        if let Some(start) = seen.insert(key(state), idx) {
+            return Cycle {
+                start,
+                length: idx - start,
+            };
+        }
+        step(state);
+    }
+    unreachable!()
+}
+
+/// Steps `state` to step `n`, skipping whole cycles as soon as the key of a state repeats.
+/// Returns the cycle, if it was found before step `n`.
+pub fn advance<S, K: Eq + Hash>(
+    state: &mut S,
+    n: usize,
+    mut step: impl FnMut(&mut S),
+    key: impl Fn(&S) -> K,
+) -> Option<Cycle> {
+    let mut seen = HashMap::new();
+    for idx in 0..n {
+        if let Some(start) = seen.insert(key(state), idx) {
+            let cycle = Cycle {
+                start,
+                length: idx - start,
+            };
+            for _ in 0..(n - idx) % cycle.length {
+                step(state);
+            }
+            return Some(cycle);
+        }
+        step(state);
+    }
+    None
+}
+
+/// Finds the cycle of `initial, next(initial), ...` with Brent's algorithm, keeping only two
+/// states at a time. Loops forever if the states never repeat.
+pub fn brent<S: Clone + PartialEq>(initial: &S, next: impl Fn(&S) -> S) -> Cycle {
+    // find the length, by moving the tortoise to the hare at every power of two.
+    let mut power = 1;
+    let mut length = 1;
+    let mut tortoise = initial.clone();
+    let mut hare = next(initial);
+    while tortoise != hare {
+        if power == length {
+            tortoise = hare.clone();
+            power *= 2;
+            length = 0;
+        }
+        hare = next(&hare);
+        length += 1;
+    }
+
+    // then the start, with the hare `length` steps ahead of the tortoise.
+    let mut tortoise = initial.clone();
+    let mut hare = initial.clone();
+    for _ in 0..length {
+        hare = next(&hare);
+    }
+    let mut start = 0;
+    while tortoise != hare {
+        tortoise = next(&tortoise);
+        hare = next(&hare);
+        start += 1;
+    }
+
+    Cycle { start, length }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 0 -> 5 -> 3, then powers of 3 modulo 100, which repeat every 20 steps.
+    fn next(value: &u32) -> u32 {
+        match value {
+            0 => 5,
+            5 => 3,
+            value => value * 3 % 100,
+        }
+    }
+
+    #[test]
+    fn finds_cycles() {
+        let expected = Cycle {
+            start: 2,
+            length: 20,
+        };
+
+        let mut state = 0;
+        assert_eq!(find_cycle(&mut state, |v| *v = next(v), |v| *v), expected);
+        assert_eq!(state, 3);
+        assert_eq!(brent(&0, next), expected);
+
+        assert_eq!(expected.reduce(1), 1);
+        assert_eq!(expected.reduce(22), 2);
+        assert_eq!(expected.reduce(45), 5);
+    }
+
+    #[test]
+    fn advances_past_cycles() {
+        let mut state = 0;
+        let cycle = advance(&mut state, 1_000_000_000, |v| *v = next(v), |v| *v);
+        assert_eq!(cycle.map(|c| c.length), Some(20));
+
+        let mut expected = 0;
+        for _ in 0..cycle.unwrap().reduce(1_000_000_000) {
+            expected = next(&expected);
+        }
+        assert_eq!(state, expected);
+
+        let mut state = 0;
+        assert_eq!(advance(&mut state, 3, |v| *v = next(v), |v| *v), None);
+        assert_eq!(state, 9);
+    }
+}
@@ -0,0 +1,101 @@
+/// Options of the step debuggers of interpreter puzzles, such as the 3-bit computer of day 17,
+/// and of the statistics that solutions can print, such as the hits of a memoization cache.
+///
+/// Like the [`crate::viz`] options, they are read from the arguments of the solution binary and
+/// forwarded by `cargo solve`, e.g. `cargo solve 17 --debug-disassemble --debug-break out`.
+/// The flags have their own `--debug-` prefix so that they never collide with the visualization.
+use std::env;
+
+/// Debugging settings, read from the command-line arguments of a solution binary.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Options {
+    /// Print the program as mnemonics before running it.
+    pub disassemble: bool,
+    /// Log the state after each instruction to `data/viz/NN/trace.txt`.
+    pub trace: bool,
+    /// Stop before every instruction.
+    pub step: bool,
+    /// Where to stop, in the syntax of the day (e.g. a program counter).
+    pub breakpoints: Vec<String>,
+    /// Print statistics, e.g. of [`crate::memoize::Memo`] caches.
+    pub stats: bool,
+}
+
+impl Options {
+    pub fn from_args(args: &[String]) -> Options {
+        // benchmarks run the solution many times, never debug while timing.
+        if args.contains(&"--time".into()) {
+            return Options::default();
+        }
+
+        let breakpoints = args
+            .iter()
+            .zip(args.iter().skip(1))
+            .filter(|(flag, _)| *flag == "--debug-break")
+            .map(|(_, value)| value.clone())
+            .collect();
+
+        Options {
+            disassemble: args.contains(&"--debug-disassemble".into()),
+            trace: args.contains(&"--debug-trace".into()),
+            step: args.contains(&"--debug-step".into()),
+            breakpoints,
+            stats: args.contains(&"--debug-stats".into()),
+        }
+    }
+
+    /// Reads the options from the arguments of the current process.
+    pub fn from_env() -> Options {
+        let args: Vec<String> = env::args().collect();
+        Options::from_args(&args)
+    }
+
+    /// Converts the options back to arguments, so they can be forwarded to a child command.
+    pub fn to_args(&self) -> Vec<String> {
+        let mut args = vec![];
+        if self.disassemble {
+            args.push("--debug-disassemble".into());
+        }
+        if self.trace {
+            args.push("--debug-trace".into());
+        }
+        if self.step {
+            args.push("--debug-step".into());
+        }
+        for breakpoint in &self.breakpoints {
+            args.push("--debug-break".into());
+            args.push(breakpoint.clone());
+        }
+        if self.stats {
+            args.push("--debug-stats".into());
+        }
+        args
+    }
+
+    /// Whether the program has to run under a debugger, instead of only being listed.
+    pub fn needs_debugger(&self) -> bool {
+        self.trace || self.step || !self.breakpoints.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn forwards_options_as_arguments() {
+        let options = Options {
+            disassemble: true,
+            trace: true,
+            step: false,
+            breakpoints: vec!["4".into(), "out".into()],
+            stats: true,
+        };
+        assert_eq!(Options::from_args(&options.to_args()), options);
+        assert!(options.needs_debugger());
+
+        let mut args = options.to_args();
+        args.push("--time".into());
+        assert_eq!(Options::from_args(&args), Options::default());
+    }
+}
@@ -0,0 +1,465 @@
+/// Undirected graphs with nodes interned to integer ids.
+///
+/// Each node keeps its neighbors in a [`BitSet`], so clique searches work on whole words
+/// instead of hashing node values.
+use std::{
+    collections::{HashMap, VecDeque},
+    hash::{Hash, Hasher},
+};
+
+pub type NodeId = usize;
+
+/// A set of node ids, stored as bits. Sets with the same ids are equal, however many words
+/// they have allocated.
+#[derive(Debug, Clone, Default)]
+pub struct BitSet {
+    words: Vec<u64>,
+}
+
+impl BitSet {
+    pub fn new() -> BitSet {
+        BitSet::default()
+    }
+
+    pub fn insert(&mut self, id: NodeId) {
+        let word = id / 64;
+        if word >= self.words.len() {
+            self.words.resize(word + 1, 0);
+        }
+        self.words[word] |= 1 << (id % 64);
+    }
+
+    pub fn remove(&mut self, id: NodeId) {
+        if let Some(word) = self.words.get_mut(id / 64) {
+            *word &= !(1 << (id % 64));
+        }
+    }
+
+    pub fn contains(&self, id: NodeId) -> bool {
+        self.words
+            .get(id / 64)
+            .is_some_and(|word| word & (1 << (id % 64)) != 0)
+    }
+
+    pub fn len(&self) -> usize {
+        self.words
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|word| *word == 0)
+    }
+
+    #[must_use]
+    pub fn intersection(&self, other: &BitSet) -> BitSet {
+        BitSet {
+            words: self
+                .words
+                .iter()
+                .zip(&other.words)
+                .map(|(a, b)| a & b)
+                .collect(),
+        }
+    }
+
+    #[must_use]
+    pub fn difference(&self, other: &BitSet) -> BitSet {
+        BitSet {
+            words: self
+                .words
+                .iter()
+                .enumerate()
+                .map(|(idx, word)| word & !other.words.get(idx).unwrap_or(&0))
+                .collect(),
+        }
+    }
+
+    /// Number of ids in both sets, without building the intersection.
+    pub fn intersection_len(&self, other: &BitSet) -> usize {
+        self.words
+            .iter()
+            .zip(&other.words)
+            .map(|(a, b)| (a & b).count_ones() as usize)
+            .sum()
+    }
+
+    /// The words up to the last one with an id, so that trailing zeros never matter.
+    fn significant_words(&self) -> &[u64] {
+        let len = self
+            .words
+            .iter()
+            .rposition(|word| *word != 0)
+            .map_or(0, |idx| idx + 1);
+        &self.words[..len]
+    }
+
+    /// Iterates over the ids in increasing order.
+    pub fn iter(&self) -> impl Iterator<Item = NodeId> + '_ {
+        self.words.iter().enumerate().flat_map(|(idx, &word)| {
+            let mut word = word;
+            std::iter::from_fn(move || {
+                if word == 0 {
+                    return None;
+                }
+                let bit = word.trailing_zeros() as usize;
+                word &= word - 1;
+                Some(idx * 64 + bit)
+            })
+        })
+    }
+}
+
+impl PartialEq for BitSet {
+    fn eq(&self, other: &BitSet) -> bool {
+        self.significant_words() == other.significant_words()
+    }
+}
+
+impl Eq for BitSet {}
+
+impl Hash for BitSet {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.significant_words().hash(state);
+    }
+}
+
+impl FromIterator<NodeId> for BitSet {
+    fn from_iter<I: IntoIterator<Item = NodeId>>(iter: I) -> Self {
+        let mut set = BitSet::new();
+        for id in iter {
+            set.insert(id);
+        }
+        set
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Graph<N> {
+    nodes: Vec<N>,
+    ids: HashMap<N, NodeId>,
+    adjacency: Vec<BitSet>,
+}
+
+impl<N> Default for Graph<N> {
+    fn default() -> Self {
+        Graph {
+            nodes: vec![],
+            ids: HashMap::new(),
+            adjacency: vec![],
+        }
+    }
+}
+
+impl<N: Clone + Eq + Hash> Graph<N> {
+    pub fn new() -> Graph<N> {
+        Graph::default()
+    }
+
+    pub fn from_edges(edges: impl IntoIterator<Item = (N, N)>) -> Graph<N> {
+        let mut graph = Graph::new();
+        for (a, b) in edges {
+            graph.add_edge(a, b);
+        }
+        graph
+    }
+
+    /// Returns the id of a node, adding it if needed.
+    pub fn add_node(&mut self, node: N) -> NodeId {
+        if let Some(&id) = self.ids.get(&node) {
+            return id;
+        }
+
+        let id = self.nodes.len();
+        self.ids.insert(node.clone(), id);
+        self.nodes.push(node);
+        self.adjacency.push(BitSet::new());
+        id
+    }
+
+    pub fn add_edge(&mut self, a: N, b: N) -> (NodeId, NodeId) {
+        let (a, b) = (self.add_node(a), self.add_node(b));
+        self.adjacency[a].insert(b);
+        self.adjacency[b].insert(a);
+        (a, b)
+    }
+
+    pub fn id(&self, node: &N) -> Option<NodeId> {
+        self.ids.get(node).copied()
+    }
+}
+
+impl<N> Graph<N> {
+    pub fn node(&self, id: NodeId) -> &N {
+        &self.nodes[id]
+    }
+
+    pub fn nodes(&self) -> &[N] {
+        &self.nodes
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    pub fn neighbors(&self, id: NodeId) -> &BitSet {
+        &self.adjacency[id]
+    }
+
+    pub fn degree(&self, id: NodeId) -> usize {
+        self.adjacency[id].len()
+    }
+
+    pub fn has_edge(&self, a: NodeId, b: NodeId) -> bool {
+        self.adjacency[a].contains(b)
+    }
+
+    /// Returns every maximal clique, with Bron–Kerbosch and pivoting.
+    pub fn maximal_cliques(&self) -> Vec<Vec<NodeId>> {
+        let mut cliques = vec![];
+        self.bron_kerbosch(
+            &mut vec![],
+            (0..self.len()).collect(),
+            BitSet::new(),
+            &mut cliques,
+        );
+        cliques
+    }
+
+    fn bron_kerbosch(
+        &self,
+        clique: &mut Vec<NodeId>,
+        mut candidates: BitSet,
+        mut excluded: BitSet,
+        cliques: &mut Vec<Vec<NodeId>>,
+    ) {
+        if candidates.is_empty() && excluded.is_empty() {
+            cliques.push(clique.clone());
+            return;
+        }
+
+        // the neighbors of the pivot are found through the pivot or one of its non-neighbors.
+        let pivot = candidates
+            .iter()
+            .chain(excluded.iter())
+            .max_by_key(|id| candidates.intersection_len(&self.adjacency[*id]))
+            .unwrap();
+
+        for id in candidates
+            .difference(&self.adjacency[pivot])
+            .iter()
+            .collect::<Vec<_>>()
+        {
+            let neighbors = &self.adjacency[id];
+            clique.push(id);
+            self.bron_kerbosch(
+                clique,
+                candidates.intersection(neighbors),
+                excluded.intersection(neighbors),
+                cliques,
+            );
+            clique.pop();
+
+            candidates.remove(id);
+            excluded.insert(id);
+        }
+    }
+
+    /// Returns a largest clique, ids in increasing order.
+    pub fn maximum_clique(&self) -> Vec<NodeId> {
+        let mut clique = self
+            .maximal_cliques()
+            .into_iter()
+            .max_by_key(Vec::len)
+            .unwrap_or_default();
+        clique.sort_unstable();
+        clique
+    }
+
+    /// Returns every clique of exactly `size` nodes, ids in increasing order.
+    pub fn cliques_of_size(&self, size: usize) -> Vec<Vec<NodeId>> {
+        let mut cliques = vec![];
+        if size > 0 {
+            self.extend_cliques(&mut vec![], (0..self.len()).collect(), size, &mut cliques);
+        }
+        cliques
+    }
+
+    fn extend_cliques(
+        &self,
+        clique: &mut Vec<NodeId>,
+        candidates: BitSet,
+        size: usize,
+        cliques: &mut Vec<Vec<NodeId>>,
+    ) {
+        for id in candidates.iter() {
+            clique.push(id);
+            if clique.len() == size {
+                cliques.push(clique.clone());
+            } else {
+                // only larger ids, so that each clique is found once.
+                let next: BitSet = candidates
+                    .intersection(&self.adjacency[id])
+                    .iter()
+                    .filter(|next| *next > id)
+                    .collect();
+                if next.len() + clique.len() >= size {
+                    self.extend_cliques(clique, next, size, cliques);
+                }
+            }
+            clique.pop();
+        }
+    }
+
+    /// Returns the connected components, each one in increasing order.
+    pub fn components(&self) -> Vec<Vec<NodeId>> {
+        let mut seen = BitSet::new();
+        let mut components = vec![];
+        for id in 0..self.len() {
+            if seen.contains(id) {
+                continue;
+            }
+
+            let mut component = self.bfs(id);
+            for node in &component {
+                seen.insert(*node);
+            }
+            component.sort_unstable();
+            components.push(component);
+        }
+        components
+    }
+
+    /// Returns the nodes reachable from `start`, in breadth-first order.
+    pub fn bfs(&self, start: NodeId) -> Vec<NodeId> {
+        self.distances(start)
+            .into_iter()
+            .map(|(id, _)| id)
+            .collect()
+    }
+
+    /// Returns the nodes reachable from `start` with their distance, in breadth-first order.
+    pub fn distances(&self, start: NodeId) -> Vec<(NodeId, usize)> {
+        let mut seen = BitSet::new();
+        seen.insert(start);
+        let mut queue = VecDeque::from([(start, 0)]);
+        let mut visited = vec![];
+
+        while let Some((id, distance)) = queue.pop_front() {
+            visited.push((id, distance));
+            for next in self.adjacency[id].iter() {
+                if !seen.contains(next) {
+                    seen.insert(next);
+                    queue.push_back((next, distance + 1));
+                }
+            }
+        }
+        visited
+    }
+
+    /// Returns the nodes reachable from `start`, in depth-first (pre-)order.
+    pub fn dfs(&self, start: NodeId) -> Vec<NodeId> {
+        let mut seen = BitSet::new();
+        let mut stack = vec![start];
+        let mut visited = vec![];
+
+        while let Some(id) = stack.pop() {
+            if seen.contains(id) {
+                continue;
+            }
+            seen.insert(id);
+            visited.push(id);
+
+            // reversed, so that smaller neighbors are visited first.
+            let neighbors: Vec<NodeId> = self.adjacency[id].iter().collect();
+            stack.extend(neighbors.into_iter().rev().filter(|n| !seen.contains(*n)));
+        }
+        visited
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(graph: &Graph<&str>, ids: &[NodeId]) -> Vec<String> {
+        let mut names: Vec<String> = ids.iter().map(|id| graph.node(*id).to_string()).collect();
+        names.sort_unstable();
+        names
+    }
+
+    #[test]
+    fn finds_cliques() {
+        // a square with one diagonal, and a node linked to a triangle of it.
+        let graph = Graph::from_edges([
+            ("a", "b"),
+            ("b", "c"),
+            ("c", "d"),
+            ("d", "a"),
+            ("a", "c"),
+            ("a", "e"),
+            ("b", "e"),
+            ("c", "e"),
+            ("e", "f"),
+        ]);
+
+        assert_eq!(graph.len(), 6);
+        assert_eq!(graph.degree(graph.id(&"a").unwrap()), 4);
+        assert_eq!(
+            names(&graph, &graph.maximum_clique()),
+            vec!["a", "b", "c", "e"]
+        );
+        assert_eq!(graph.maximal_cliques().len(), 3);
+
+        let triangles: Vec<Vec<String>> = graph
+            .cliques_of_size(3)
+            .iter()
+            .map(|clique| names(&graph, clique))
+            .collect();
+        assert_eq!(
+            triangles,
+            vec![
+                vec!["a", "b", "c"],
+                vec!["a", "b", "e"],
+                vec!["a", "c", "d"],
+                vec!["a", "c", "e"],
+                vec!["b", "c", "e"]
+            ]
+        );
+        assert_eq!(graph.cliques_of_size(2).len(), 9);
+    }
+
+    #[test]
+    fn compares_sets_by_ids() {
+        use std::collections::HashSet;
+
+        let high: BitSet = [1, 200].into_iter().collect();
+        let low: BitSet = [1, 2].into_iter().collect();
+        let common = high.intersection(&low);
+        assert_eq!(common, [1].into_iter().collect());
+
+        let mut emptied = high.clone();
+        emptied.remove(200);
+        emptied.remove(1);
+        assert_eq!(emptied, BitSet::new());
+        assert_eq!(high.difference(&high), BitSet::new());
+
+        let sets: HashSet<BitSet> = [common, BitSet::from_iter([1]), emptied, BitSet::new()].into();
+        assert_eq!(sets.len(), 2);
+    }
+
+    #[test]
+    fn traverses_components() {
+        let graph = Graph::from_edges([(0, 1), (1, 2), (0, 3), (4, 5)]);
+
+        assert_eq!(graph.components(), vec![vec![0, 1, 2, 3], vec![4, 5]]);
+        assert_eq!(graph.bfs(0), vec![0, 1, 3, 2]);
+        assert_eq!(graph.dfs(0), vec![0, 1, 2, 3]);
+        assert_eq!(graph.distances(2), vec![(2, 0), (1, 1), (0, 2), (3, 3)]);
+    }
+}
@@ -0,0 +1,475 @@
+/// Algorithms operating on [`Map2D`] grids: flood fill, connected components, region metrics,
+/// transformations and sub-grid search.
+use std::{collections::VecDeque, iter};
+
+use crate::{Map2D, Pos2D};
+
+/// Orthogonal offsets, in clockwise order starting from the top.
+const ORTHOGONAL: [(i32, i32); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+
+/// Inclusive rectangle enclosing a set of positions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BoundingBox {
+    pub min: Pos2D,
+    pub max: Pos2D,
+}
+
+impl BoundingBox {
+    pub fn width(&self) -> usize {
+        (self.max.x - self.min.x + 1) as usize
+    }
+
+    pub fn height(&self) -> usize {
+        (self.max.y - self.min.y + 1) as usize
+    }
+
+    pub fn contains(&self, position: &Pos2D) -> bool {
+        position.x >= self.min.x
+            && position.x <= self.max.x
+            && position.y >= self.min.y
+            && position.y <= self.max.y
+    }
+}
+
+/// A connected set of tiles along with its metrics.
+#[derive(Debug, Clone)]
+pub struct Region {
+    pub label: usize,
+    pub positions: Vec<Pos2D>,
+    pub area: usize,
+    pub perimeter: usize,
+    /// Number of straight fence sides, which is always equal to the number of corners.
+    pub sides: usize,
+    pub bbox: BoundingBox,
+}
+
+/// Result of labelling a grid: every tile holds the label of the component it belongs to.
+#[derive(Debug)]
+pub struct Components {
+    pub labels: Map2D<usize>,
+    pub count: usize,
+}
+
+impl<T> Map2D<T> {
+    /// Returns the in-bounds orthogonal neighbors of `position`.
+    pub fn orthogonal_neighbors(&self, position: &Pos2D) -> impl Iterator<Item = Pos2D> + '_ {
+        let position = *position;
+        ORTHOGONAL
+            .iter()
+            .map(move |offset| position.add(offset))
+            .filter(|neighbor| self.is_valid_pos(neighbor))
+    }
+
+    /// Returns every position reachable from `start` through orthogonal moves,
+    /// where `connected(from, to)` decides whether a move between two tiles is allowed.
+    pub fn flood_fill(&self, start: &Pos2D, connected: impl Fn(&T, &T) -> bool) -> Vec<Pos2D> {
+        let Some(start_idx) = self.pos2idx(start) else {
+            return vec![];
+        };
+
+        let mut visited = vec![false; self.tiles.len()];
+        visited[start_idx] = true;
+        self.fill_from(start_idx, &connected, &mut visited)
+            .into_iter()
+            .map(|idx| self.idx2pos(idx))
+            .collect()
+    }
+
+    /// Assigns a label to each tile so that two orthogonally adjacent tiles share a label
+    /// if and only if they are linked by `connected`.
+    pub fn label_components(&self, connected: impl Fn(&T, &T) -> bool) -> Components {
+        let mut labels = vec![0; self.tiles.len()];
+        let mut visited = vec![false; self.tiles.len()];
+        let mut count = 0;
+
+        for idx in 0..self.tiles.len() {
+            if visited[idx] {
+                continue;
+            }
+
+            visited[idx] = true;
+            for member in self.fill_from(idx, &connected, &mut visited) {
+                labels[member] = count;
+            }
+            count += 1;
+        }
+
+        Components {
+            labels: Map2D {
+                width: self.width,
+                height: self.height,
+                tiles: labels,
+            },
+            count,
+        }
+    }
+
+    /// Splits the grid into connected regions and computes the metrics of each one.
+    pub fn regions(&self, connected: impl Fn(&T, &T) -> bool) -> Vec<Region> {
+        let components = self.label_components(connected);
+
+        let mut members: Vec<Vec<Pos2D>> = vec![vec![]; components.count];
+        for (idx, &label) in components.labels.tiles.iter().enumerate() {
+            members[label].push(self.idx2pos(idx));
+        }
+
+        members
+            .into_iter()
+            .enumerate()
+            .map(|(label, positions)| components.region_metrics(label, positions))
+            .collect()
+    }
+
+    /// Breadth-first traversal from an already visited tile, returns the indexes of the filled tiles.
+    fn fill_from(
+        &self,
+        start_idx: usize,
+        connected: &impl Fn(&T, &T) -> bool,
+        visited: &mut [bool],
+    ) -> Vec<usize> {
+        let mut filled = vec![start_idx];
+        let mut queue = VecDeque::from([start_idx]);
+
+        while let Some(idx) = queue.pop_front() {
+            let position = self.idx2pos(idx);
+            for neighbor in self.orthogonal_neighbors(&position) {
+                let neighbor_idx = self.pos2idx(&neighbor).unwrap();
+                if visited[neighbor_idx] || !connected(&self.tiles[idx], &self.tiles[neighbor_idx])
+                {
+                    continue;
+                }
+
+                visited[neighbor_idx] = true;
+                filled.push(neighbor_idx);
+                queue.push_back(neighbor_idx);
+            }
+        }
+
+        filled
+    }
+}
+
+impl Map2D<char> {
+    /// Builds a grid from text, one row per line.
+    pub fn from_chars(input: &str) -> Map2D<char> {
+        Map2D {
+            width: input.lines().next().map_or(0, |line| line.chars().count()),
+            height: input.lines().count(),
+            tiles: input.lines().flat_map(|line| line.chars()).collect(),
+        }
+    }
+}
+
+impl<T> Map2D<T> {
+    /// Applies `f` to every tile, keeping the layout of the grid.
+    pub fn map<U>(&self, f: impl Fn(&T) -> U) -> Map2D<U> {
+        Map2D {
+            width: self.width,
+            height: self.height,
+            tiles: self.tiles.iter().map(f).collect(),
+        }
+    }
+
+    /// Walks from `start` (included) by `step` until leaving the grid.
+    pub fn ray(&self, start: &Pos2D, step: &Pos2D) -> impl Iterator<Item = &T> + '_ {
+        let step = *step;
+        iter::successors(Some(*start), move |position| Some(position.add(step)))
+            .map_while(|position| self.get(&position))
+    }
+
+    pub fn row(&self, y: usize) -> impl Iterator<Item = &T> + '_ {
+        self.ray(&Pos2D::new(0, y as i32), &Pos2D::new(1, 0))
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> + '_ {
+        self.ray(&Pos2D::new(x as i32, 0), &Pos2D::new(0, 1))
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = impl Iterator<Item = &T> + '_> + '_ {
+        (0..self.height).map(|y| self.row(y))
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T> + '_> + '_ {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    /// Top-left to bottom-right diagonals, starting from the bottom-left corner.
+    pub fn diagonals(&self) -> impl Iterator<Item = impl Iterator<Item = &T> + '_> + '_ {
+        let (width, height) = (self.width as i32, self.height as i32);
+        let starts = (0..height)
+            .rev()
+            .map(|y| Pos2D::new(0, y))
+            .chain((1..width).map(|x| Pos2D::new(x, 0)));
+
+        starts.map(|start| self.ray(&start, &Pos2D::new(1, 1)))
+    }
+
+    /// Top-right to bottom-left diagonals, starting from the top-left corner.
+    pub fn anti_diagonals(&self) -> impl Iterator<Item = impl Iterator<Item = &T> + '_> + '_ {
+        let (width, height) = (self.width as i32, self.height as i32);
+        let starts = (0..width)
+            .map(|x| Pos2D::new(x, 0))
+            .chain((1..height).map(move |y| Pos2D::new(width - 1, y)));
+
+        starts.map(|start| self.ray(&start, &Pos2D::new(-1, 1)))
+    }
+}
+
+impl<T: Clone> Map2D<T> {
+    /// Builds a new grid of the given size where each tile is copied from
+    /// the position returned by `source` in `self`.
+    fn remap(&self, width: usize, height: usize, source: impl Fn(i32, i32) -> Pos2D) -> Map2D<T> {
+        let tiles = (0..height as i32)
+            .flat_map(|y| (0..width as i32).map(move |x| (x, y)))
+            .map(|(x, y)| self.get(&source(x, y)).unwrap().clone())
+            .collect();
+
+        Map2D {
+            width,
+            height,
+            tiles,
+        }
+    }
+
+    pub fn transpose(&self) -> Map2D<T> {
+        self.remap(self.height, self.width, |x, y| Pos2D::new(y, x))
+    }
+
+    /// Rotates the grid clockwise by 90 degrees.
+    pub fn rotate_90(&self) -> Map2D<T> {
+        let height = self.height as i32;
+        self.remap(self.height, self.width, |x, y| {
+            Pos2D::new(y, height - 1 - x)
+        })
+    }
+
+    pub fn rotate_180(&self) -> Map2D<T> {
+        let (width, height) = (self.width as i32, self.height as i32);
+        self.remap(self.width, self.height, |x, y| {
+            Pos2D::new(width - 1 - x, height - 1 - y)
+        })
+    }
+
+    /// Rotates the grid clockwise by 270 degrees (i.e. counter-clockwise by 90 degrees).
+    pub fn rotate_270(&self) -> Map2D<T> {
+        let width = self.width as i32;
+        self.remap(self.height, self.width, |x, y| Pos2D::new(width - 1 - y, x))
+    }
+
+    /// Mirrors the grid along the vertical axis: left and right are swapped.
+    pub fn flip_horizontal(&self) -> Map2D<T> {
+        let width = self.width as i32;
+        self.remap(self.width, self.height, |x, y| Pos2D::new(width - 1 - x, y))
+    }
+
+    /// Mirrors the grid along the horizontal axis: top and bottom are swapped.
+    pub fn flip_vertical(&self) -> Map2D<T> {
+        let height = self.height as i32;
+        self.remap(self.width, self.height, |x, y| {
+            Pos2D::new(x, height - 1 - y)
+        })
+    }
+
+    /// Extracts the tiles inside `bbox`, returns [`None`] if it does not fit in the grid or if
+    /// its corners are swapped.
+    pub fn crop(&self, bbox: &BoundingBox) -> Option<Map2D<T>> {
+        if !self.is_valid_pos(&bbox.min)
+            || !self.is_valid_pos(&bbox.max)
+            || bbox.min.x > bbox.max.x
+            || bbox.min.y > bbox.max.y
+        {
+            return None;
+        }
+
+        let min = bbox.min;
+        Some(self.remap(bbox.width(), bbox.height(), |x, y| min.right(x).bot(y)))
+    }
+}
+
+impl<T: PartialEq> Map2D<T> {
+    /// Returns whether `pattern` matches the grid with its top-left corner at `origin`.
+    /// [`None`] tiles of the pattern are wildcards.
+    pub fn matches_at(&self, pattern: &Map2D<Option<T>>, origin: &Pos2D) -> bool {
+        pattern.tiles.iter().enumerate().all(|(idx, expected)| {
+            let position = origin.add(pattern.idx2pos(idx));
+            match (expected, self.get(&position)) {
+                (_, None) => false,
+                (None, Some(_)) => true,
+                (Some(expected), Some(tile)) => expected == tile,
+            }
+        })
+    }
+
+    /// Returns the top-left corner of every occurrence of `pattern` in the grid.
+    pub fn find_pattern(&self, pattern: &Map2D<Option<T>>) -> Vec<Pos2D> {
+        if pattern.width > self.width || pattern.height > self.height {
+            return vec![];
+        }
+
+        (0..=self.height - pattern.height)
+            .flat_map(|y| {
+                (0..=self.width - pattern.width).map(move |x| Pos2D::new(x as i32, y as i32))
+            })
+            .filter(|origin| self.matches_at(pattern, origin))
+            .collect()
+    }
+}
+
+impl Components {
+    fn same_label(&self, position: &Pos2D, label: usize) -> bool {
+        self.labels.get(position) == Some(&label)
+    }
+
+    fn region_metrics(&self, label: usize, positions: Vec<Pos2D>) -> Region {
+        let mut perimeter = 0;
+        let mut corners = 0;
+        let mut min = positions[0];
+        let mut max = positions[0];
+
+        for position in positions.iter() {
+            min = Pos2D::new(min.x.min(position.x), min.y.min(position.y));
+            max = Pos2D::new(max.x.max(position.x), max.y.max(position.y));
+
+            let inside: Vec<bool> = ORTHOGONAL
+                .iter()
+                .map(|offset| self.same_label(&position.add(offset), label))
+                .collect();
+
+            perimeter += inside.iter().filter(|&&is_inside| !is_inside).count();
+
+            // check each corner formed by two consecutive orthogonal directions
+            for dir in 0..ORTHOGONAL.len() {
+                let next = (dir + 1) % ORTHOGONAL.len();
+                let (dx1, dy1) = ORTHOGONAL[dir];
+                let (dx2, dy2) = ORTHOGONAL[next];
+                let diagonal = position.add((dx1 + dx2, dy1 + dy2));
+
+                let is_convex = !inside[dir] && !inside[next];
+                let is_concave = inside[dir] && inside[next] && !self.same_label(&diagonal, label);
+                if is_convex || is_concave {
+                    corners += 1;
+                }
+            }
+        }
+
+        Region {
+            label,
+            area: positions.len(),
+            perimeter,
+            sides: corners,
+            bbox: BoundingBox { min, max },
+            positions,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn to_string(map: &Map2D<char>) -> String {
+        map.rows()
+            .map(|row| row.collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn computes_region_metrics() {
+        let map = Map2D::from_chars("AAAA\nBBCD\nBBCC\nEEEC");
+        let regions = map.regions(|a, b| a == b);
+
+        let metrics: Vec<_> = regions
+            .iter()
+            .map(|r| {
+                (
+                    map.get(&r.positions[0]).copied().unwrap(),
+                    r.area,
+                    r.perimeter,
+                    r.sides,
+                )
+            })
+            .collect();
+
+        assert_eq!(
+            metrics,
+            vec![
+                ('A', 4, 10, 4),
+                ('B', 4, 8, 4),
+                ('C', 4, 10, 8),
+                ('D', 1, 4, 4),
+                ('E', 3, 8, 4),
+            ]
+        );
+    }
+
+    #[test]
+    fn counts_inner_sides() {
+        let map = Map2D::from_chars("AAAAAA\nAAABBA\nAAABBA\nABBAAA\nABBAAA\nAAAAAA");
+        let regions = map.regions(|a, b| a == b);
+        let a_region = regions.iter().find(|r| r.area == 28).unwrap();
+
+        assert_eq!(a_region.sides, 12);
+        assert_eq!(a_region.bbox.width(), 6);
+        assert_eq!(a_region.bbox.height(), 6);
+    }
+
+    #[test]
+    fn flood_fills_non_square_grid() {
+        let map = Map2D::from_chars("..#..\n..#..");
+        let filled = map.flood_fill(&Pos2D::new(0, 0), |a, b| a == b);
+
+        assert_eq!(filled.len(), 4);
+        assert!(filled.iter().all(|p| p.x < 2));
+    }
+
+    #[test]
+    fn rotates_and_flips() {
+        let map = Map2D::from_chars("abc\ndef");
+
+        assert_eq!(to_string(&map.rotate_90()), "da\neb\nfc");
+        assert_eq!(to_string(&map.rotate_180()), "fed\ncba");
+        assert_eq!(to_string(&map.rotate_270()), "cf\nbe\nad");
+        assert_eq!(to_string(&map.transpose()), "ad\nbe\ncf");
+        assert_eq!(to_string(&map.flip_horizontal()), "cba\nfed");
+        assert_eq!(to_string(&map.flip_vertical()), "def\nabc");
+    }
+
+    #[test]
+    fn extracts_lines_and_crops() {
+        let map = Map2D::from_chars("abc\ndef\nghi");
+
+        let columns: Vec<String> = map.columns().map(|c| c.collect()).collect();
+        let diagonals: Vec<String> = map.diagonals().map(|d| d.collect()).collect();
+        let anti_diagonals: Vec<String> = map.anti_diagonals().map(|d| d.collect()).collect();
+
+        assert_eq!(columns, ["adg", "beh", "cfi"]);
+        assert_eq!(diagonals, ["g", "dh", "aei", "bf", "c"]);
+        assert_eq!(anti_diagonals, ["a", "bd", "ceg", "fh", "i"]);
+
+        let bbox = BoundingBox {
+            min: Pos2D::new(1, 1),
+            max: Pos2D::new(2, 2),
+        };
+        assert_eq!(to_string(&map.crop(&bbox).unwrap()), "ef\nhi");
+
+        let swapped = BoundingBox {
+            min: Pos2D::new(2, 0),
+            max: Pos2D::new(1, 2),
+        };
+        assert!(map.crop(&swapped).is_none());
+        let swapped = BoundingBox {
+            min: Pos2D::new(0, 2),
+            max: Pos2D::new(1, 1),
+        };
+        assert!(map.crop(&swapped).is_none());
+    }
+
+    #[test]
+    fn finds_pattern_with_wildcards() {
+        let map = Map2D::from_chars("MMSM\nXAMA\nMSSS");
+        let pattern = Map2D::from_chars("M.S\n.A.\nM.S").map(|&c| (c != '.').then_some(c));
+
+        assert_eq!(map.find_pattern(&pattern), vec![Pos2D::new(0, 0)]);
+    }
+}
@@ -1,8 +1,19 @@
+pub mod circuit;
+pub mod cycle;
+pub mod debug;
+pub mod graph;
+pub mod grid;
+pub mod linalg;
+pub mod math;
+pub mod memoize;
+pub mod order;
+pub mod parse;
 pub mod template;
+pub mod viz;
 
 // Use this file to add helper functions and additional modules.
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Pos2D {
     pub x: i32,
     pub y: i32,
@@ -66,12 +77,6 @@ impl Pos2D {
     }
 }
 
-impl PartialEq for Pos2D {
-    fn eq(&self, other: &Self) -> bool {
-        self.x == other.x && self.y == other.y
-    }
-}
-
 impl AsRef<Pos2D> for Pos2D {
     fn as_ref(&self) -> &Pos2D {
         self
@@ -84,7 +89,7 @@ impl AsRef<Pos2D> for (i32, i32) {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Map2D<T> {
     pub width: usize,
     pub height: usize,
@@ -144,11 +149,11 @@ pub fn pos2idx(position: &Pos2D, width: usize, height: usize) -> Option<usize> {
         return None;
     }
 
-    Some((position.y as usize * height) + position.x as usize)
+    Some((position.y as usize * width) + position.x as usize)
 }
 
-pub fn idx2pos(idx: usize, width: usize, height: usize) -> Pos2D {
+pub fn idx2pos(idx: usize, width: usize, _height: usize) -> Pos2D {
     let x = (idx % width) as i32;
-    let y = (idx / height) as i32;
+    let y = (idx / width) as i32;
     Pos2D::new(x, y)
 }
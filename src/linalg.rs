@@ -0,0 +1,405 @@
+/// Exact linear algebra on integers, for systems such as the claw machines of day 13.
+///
+/// Everything is computed with `i128` and [`Rational`]s, so large coordinates (e.g. offsets of
+/// 10^13) do not lose precision the way floating point does.
+use std::{
+    cmp::Ordering,
+    fmt::Display,
+    ops::{Add, Div, Mul, Neg, Sub},
+};
+
+/// A fraction in lowest terms, with a positive denominator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rational {
+    num: i128,
+    den: i128,
+}
+
+impl Rational {
+    pub const ZERO: Rational = Rational { num: 0, den: 1 };
+    pub const ONE: Rational = Rational { num: 1, den: 1 };
+
+    /// Panics if `den` is zero, like an integer division.
+    pub fn new(num: i128, den: i128) -> Rational {
+        assert!(den != 0, "denominator of a rational cannot be zero");
+        let divisor = gcd(num, den) * den.signum();
+        Rational {
+            num: num / divisor,
+            den: den / divisor,
+        }
+    }
+
+    pub fn integer(value: i128) -> Rational {
+        Rational { num: value, den: 1 }
+    }
+
+    pub fn numerator(&self) -> i128 {
+        self.num
+    }
+
+    pub fn denominator(&self) -> i128 {
+        self.den
+    }
+
+    pub fn is_integer(&self) -> bool {
+        self.den == 1
+    }
+
+    /// Returns the value if it is an integer.
+    pub fn to_integer(&self) -> Option<i128> {
+        self.is_integer().then_some(self.num)
+    }
+}
+
+impl From<i128> for Rational {
+    fn from(value: i128) -> Self {
+        Rational::integer(value)
+    }
+}
+
+impl Display for Rational {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.is_integer() {
+            write!(f, "{}", self.num)
+        } else {
+            write!(f, "{}/{}", self.num, self.den)
+        }
+    }
+}
+
+impl Add for Rational {
+    type Output = Rational;
+
+    fn add(self, rhs: Rational) -> Rational {
+        Rational::new(self.num * rhs.den + rhs.num * self.den, self.den * rhs.den)
+    }
+}
+
+impl Sub for Rational {
+    type Output = Rational;
+
+    fn sub(self, rhs: Rational) -> Rational {
+        self + -rhs
+    }
+}
+
+impl Mul for Rational {
+    type Output = Rational;
+
+    fn mul(self, rhs: Rational) -> Rational {
+        Rational::new(self.num * rhs.num, self.den * rhs.den)
+    }
+}
+
+impl Div for Rational {
+    type Output = Rational;
+
+    /// Panics when dividing by zero.
+    fn div(self, rhs: Rational) -> Rational {
+        Rational::new(self.num * rhs.den, self.den * rhs.num)
+    }
+}
+
+impl Neg for Rational {
+    type Output = Rational;
+
+    fn neg(self) -> Rational {
+        Rational {
+            num: -self.num,
+            den: self.den,
+        }
+    }
+}
+
+impl PartialOrd for Rational {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Rational {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // denominators are positive, cross-multiplying keeps the order.
+        (self.num * other.den).cmp(&(other.num * self.den))
+    }
+}
+
+/// Greatest common divisor, always non-negative.
+fn gcd(a: i128, b: i128) -> i128 {
+    let (mut a, mut b) = (a.abs(), b.abs());
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// Returns `(g, x, y)` such that `a * x + b * y = g`, where `g` is the non-negative gcd.
+pub fn ext_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (1, 0);
+    let (mut old_y, mut y) = (0, 1);
+
+    while r != 0 {
+        let quotient = old_r / r;
+        (old_r, r) = (r, old_r - quotient * r);
+        (old_x, x) = (x, old_x - quotient * x);
+        (old_y, y) = (y, old_y - quotient * y);
+    }
+
+    if old_r < 0 {
+        (-old_r, -old_x, -old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+fn div_floor(a: i128, b: i128) -> i128 {
+    let quotient = a / b;
+    if a % b != 0 && (a < 0) != (b < 0) {
+        quotient - 1
+    } else {
+        quotient
+    }
+}
+
+fn div_ceil(a: i128, b: i128) -> i128 {
+    -div_floor(-a, b)
+}
+
+/// Determinant of a square matrix, computed exactly with the Bareiss algorithm.
+pub fn determinant(matrix: &[Vec<i128>]) -> i128 {
+    let size = matrix.len();
+    let mut m = matrix.to_vec();
+    let mut sign = 1;
+    let mut previous_pivot = 1;
+
+    for k in 0..size {
+        if m[k][k] == 0 {
+            let Some(swap) = (k + 1..size).find(|row| m[*row][k] != 0) else {
+                return 0;
+            };
+            m.swap(k, swap);
+            sign = -sign;
+        }
+
+        for i in k + 1..size {
+            for j in k + 1..size {
+                // exact division, a property of the algorithm.
+                m[i][j] = (m[i][j] * m[k][k] - m[i][k] * m[k][j]) / previous_pivot;
+            }
+        }
+        previous_pivot = m[k][k];
+    }
+
+    if size == 0 {
+        1
+    } else {
+        sign * m[size - 1][size - 1]
+    }
+}
+
+/// Solves `matrix * x = rhs` with Cramer's rule, meant for small systems.
+/// Returns [`None`] if the determinant is zero, the system then has no or infinitely many
+/// solutions.
+pub fn solve_cramer(matrix: &[Vec<i128>], rhs: &[i128]) -> Option<Vec<Rational>> {
+    let det = determinant(matrix);
+    if det == 0 {
+        return None;
+    }
+
+    let solution = (0..matrix.len())
+        .map(|column| {
+            let replaced: Vec<Vec<i128>> = matrix
+                .iter()
+                .zip(rhs)
+                .map(|(row, value)| {
+                    let mut row = row.clone();
+                    row[column] = *value;
+                    row
+                })
+                .collect();
+            Rational::new(determinant(&replaced), det)
+        })
+        .collect();
+    Some(solution)
+}
+
+/// Integer solutions of `a * x + b * y = c`: `(x0 + t * step_x, y0 + t * step_y)` for any
+/// integer `t`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DiophantineSolutions {
+    pub x0: i128,
+    pub y0: i128,
+    pub step_x: i128,
+    pub step_y: i128,
+}
+
+impl DiophantineSolutions {
+    pub fn at(&self, t: i128) -> (i128, i128) {
+        (self.x0 + t * self.step_x, self.y0 + t * self.step_y)
+    }
+
+    /// Range of `t` for which both `x` and `y` are non-negative, bounds are [`None`] when
+    /// unbounded. Returns [`None`] if there is no such `t`.
+    pub fn non_negative_range(&self) -> Option<(Option<i128>, Option<i128>)> {
+        let (mut low, mut high): (Option<i128>, Option<i128>) = (None, None);
+        for (start, step) in [(self.x0, self.step_x), (self.y0, self.step_y)] {
+            match step.cmp(&0) {
+                Ordering::Greater => {
+                    let bound = div_ceil(-start, step);
+                    low = Some(low.map_or(bound, |low| low.max(bound)));
+                }
+                Ordering::Less => {
+                    let bound = div_floor(start, -step);
+                    high = Some(high.map_or(bound, |high| high.min(bound)));
+                }
+                Ordering::Equal if start < 0 => return None,
+                Ordering::Equal => {}
+            }
+        }
+
+        match (low, high) {
+            (Some(low), Some(high)) if low > high => None,
+            range => Some(range),
+        }
+    }
+}
+
+/// Solves the linear Diophantine equation `a * x + b * y = c`.
+/// Returns [`None`] if it has no integer solution.
+pub fn solve_diophantine(a: i128, b: i128, c: i128) -> Option<DiophantineSolutions> {
+    if a == 0 && b == 0 {
+        return (c == 0).then_some(DiophantineSolutions {
+            x0: 0,
+            y0: 0,
+            step_x: 1,
+            step_y: 0,
+        });
+    }
+
+    let (g, x, y) = ext_gcd(a, b);
+    if c % g != 0 {
+        return None;
+    }
+
+    let factor = c / g;
+    Some(DiophantineSolutions {
+        x0: x * factor,
+        y0: y * factor,
+        step_x: b / g,
+        step_y: -a / g,
+    })
+}
+
+/// Returns the non-negative solution of `a * x + b * y = c` with the lowest
+/// `cost_x * x + cost_y * y`. Returns [`None`] without non-negative solution, or when the cost
+/// has no minimum.
+pub fn min_cost_diophantine(
+    a: i128,
+    b: i128,
+    c: i128,
+    cost_x: i128,
+    cost_y: i128,
+) -> Option<(i128, i128)> {
+    let solutions = solve_diophantine(a, b, c)?;
+    let (low, high) = solutions.non_negative_range()?;
+
+    // the cost changes linearly with `t`, so the minimum is at one end of the range.
+    let slope = cost_x * solutions.step_x + cost_y * solutions.step_y;
+    let t = match slope.cmp(&0) {
+        Ordering::Greater => low?,
+        Ordering::Less => high?,
+        Ordering::Equal => low.or(high).unwrap_or(0),
+    };
+    Some(solutions.at(t))
+}
+
+/// Returns the non-negative integer solution of a 2x2 system with the lowest
+/// `cost[0] * x + cost[1] * y`. When the determinant is zero, the equations are either
+/// inconsistent or the same up to a factor, and the latter has many solutions to choose from.
+pub fn min_cost_2x2(
+    matrix: [[i128; 2]; 2],
+    rhs: [i128; 2],
+    cost: [i128; 2],
+) -> Option<(i128, i128)> {
+    let rows: Vec<Vec<i128>> = matrix.iter().map(|row| row.to_vec()).collect();
+    if let Some(solution) = solve_cramer(&rows, &rhs) {
+        let x = solution[0].to_integer()?;
+        let y = solution[1].to_integer()?;
+        return (x >= 0 && y >= 0).then_some((x, y));
+    }
+
+    // a row with a coefficient, all rows have to be multiples of it.
+    let Some(pivot) = (0..2).find(|row| matrix[*row] != [0, 0]) else {
+        return (rhs == [0, 0]).then_some((0, 0));
+    };
+    let [a, b] = matrix[pivot];
+    let c = rhs[pivot];
+    let consistent = (0..2).all(|row| {
+        let [ra, rb] = matrix[row];
+        a * rb == b * ra && a * rhs[row] == c * ra && b * rhs[row] == c * rb
+    });
+    if !consistent {
+        return None;
+    }
+
+    min_cost_diophantine(a, b, c, cost[0], cost[1])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn computes_with_rationals() {
+        let half = Rational::new(2, -4);
+        assert_eq!((half.numerator(), half.denominator()), (-1, 2));
+        assert_eq!(half + Rational::ONE, Rational::new(1, 2));
+        assert_eq!((half * Rational::integer(-4)).to_integer(), Some(2));
+        assert!(half < Rational::ZERO);
+        assert_eq!(Rational::new(3, 6).to_string(), "1/2");
+    }
+
+    #[test]
+    fn solves_systems_exactly() {
+        assert_eq!(
+            determinant(&[vec![2, 0, 1], vec![1, 3, 2], vec![1, 1, 2]]),
+            6
+        );
+        assert_eq!(determinant(&[vec![0, 1], vec![1, 0]]), -1);
+        assert_eq!(determinant(&[vec![1, 2], vec![2, 4]]), 0);
+
+        let offset = 10_000_000_000_000;
+        let solution = solve_cramer(
+            &[vec![26, 67], vec![66, 21]],
+            &[12748 + offset, 12176 + offset],
+        );
+        assert_eq!(
+            solution.map(|s| s.iter().map(Rational::to_integer).collect::<Vec<_>>()),
+            Some(vec![Some(118679050709), Some(103199174542)])
+        );
+    }
+
+    #[test]
+    fn solves_diophantine_equations() {
+        let (g, x, y) = ext_gcd(240, 46);
+        assert_eq!((g, 240 * x + 46 * y), (2, 2));
+        assert_eq!(solve_diophantine(4, 6, 7), None);
+
+        // 3x + 5y = 22: (4, 2) costs 4 + 2 * 3, (9, -1) is negative.
+        assert_eq!(min_cost_diophantine(3, 5, 22, 1, 3), Some((4, 2)));
+        assert_eq!(min_cost_diophantine(3, 5, 22, 3, 1), Some((4, 2)));
+        assert_eq!(min_cost_diophantine(3, 5, 1, 1, 1), None);
+
+        // both equations are the same up to a factor: 2a + 4b = 20.
+        assert_eq!(
+            min_cost_2x2([[1, 2], [2, 4]], [10, 20], [3, 1]),
+            Some((0, 5))
+        );
+        assert_eq!(min_cost_2x2([[1, 2], [2, 4]], [10, 21], [3, 1]), None);
+        assert_eq!(
+            min_cost_2x2([[94, 22], [34, 67]], [8400, 5400], [3, 1]),
+            Some((80, 40))
+        );
+    }
+}
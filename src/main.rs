@@ -1,4 +1,6 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
+use advent_of_code::template::commands::{
+    all, download, example, read, scaffold, solve, time, watch,
+};
 use args::{parse, AppArguments};
 
 #[cfg(feature = "today")]
@@ -7,30 +9,47 @@ use advent_of_code::template::Day;
 use std::process;
 
 mod args {
-    use advent_of_code::template::Day;
+    use advent_of_code::{
+        debug,
+        template::{commands::scaffold, input::InputSource, Day},
+        viz,
+    };
     use std::process;
 
     pub enum AppArguments {
         Download {
             day: Day,
+            force: bool,
         },
         Read {
             day: Day,
         },
+        Example {
+            day: Day,
+            yes: bool,
+        },
         Scaffold {
             day: Day,
             download: bool,
             overwrite: bool,
+            template: String,
         },
         Solve {
             day: Day,
             release: bool,
             dhat: bool,
             submit: Option<u8>,
+            visualize: viz::Options,
+            debug: debug::Options,
+            input: InputSource,
         },
         All {
             release: bool,
         },
+        WatchDay {
+            day: Day,
+            features: Option<String>,
+        },
         Time {
             all: bool,
             day: Option<Day>,
@@ -57,22 +76,52 @@ mod args {
                     store,
                 }
             }
+            Some("watch-day") => AppArguments::WatchDay {
+                features: args.opt_value_from_str("--features")?,
+                day: args.free_from_str()?,
+            },
             Some("download") => AppArguments::Download {
                 day: args.free_from_str()?,
+                force: args.contains("--force"),
             },
             Some("read") => AppArguments::Read {
                 day: args.free_from_str()?,
             },
+            Some("example") => AppArguments::Example {
+                day: args.free_from_str()?,
+                yes: args.contains("--yes"),
+            },
             Some("scaffold") => AppArguments::Scaffold {
                 day: args.free_from_str()?,
                 download: args.contains("--download"),
                 overwrite: args.contains("--overwrite"),
+                template: args
+                    .opt_value_from_str("--template")?
+                    .unwrap_or_else(|| scaffold::DEFAULT_TEMPLATE.to_string()),
             },
             Some("solve") => AppArguments::Solve {
                 day: args.free_from_str()?,
                 release: args.contains("--release"),
                 submit: args.opt_value_from_str("--submit")?,
                 dhat: args.contains("--dhat"),
+                visualize: viz::Options {
+                    enabled: args.contains("--visualize"),
+                    fps: args.opt_value_from_str("--fps")?,
+                    step: args.contains("--step"),
+                    dump: args.contains("--dump"),
+                    export: args.opt_value_from_str("--export")?,
+                    scale: args.opt_value_from_str("--scale")?,
+                    diagram: args.contains("--diagram"),
+                },
+                debug: debug::Options {
+                    disassemble: args.contains("--debug-disassemble"),
+                    trace: args.contains("--debug-trace"),
+                    step: args.contains("--debug-step"),
+                    breakpoints: args.values_from_str("--debug-break")?,
+                    stats: args.contains("--debug-stats"),
+                },
+                // parsed last, `--example` takes the next argument as its optional name.
+                input: parse_input_source(&mut args)?,
             },
             #[cfg(feature = "today")]
             Some("today") => AppArguments::Today,
@@ -93,6 +142,25 @@ mod args {
 
         Ok(app_args)
     }
+
+    fn parse_input_source(
+        args: &mut pico_args::Arguments,
+    ) -> Result<InputSource, Box<dyn std::error::Error>> {
+        let path: Option<String> = args.opt_value_from_str("--input")?;
+        let example = match args.opt_value_from_str::<_, String>("--example") {
+            Ok(name) => name.map(Some),
+            Err(pico_args::Error::OptionWithoutAValue(_)) => Some(None),
+            Err(e) => return Err(e.into()),
+        };
+
+        match (path, example) {
+            (Some(_), Some(_)) => Err("`--input` and `--example` cannot be used together".into()),
+            (Some(path), None) if path == "-" => Ok(InputSource::Stdin),
+            (Some(path), None) => Ok(InputSource::File(path.into())),
+            (None, Some(name)) => Ok(InputSource::Example(name)),
+            (None, None) => Ok(InputSource::Puzzle),
+        }
+    }
 }
 
 fn main() {
@@ -104,12 +172,23 @@ fn main() {
         Ok(args) => match args {
             AppArguments::All { release } => all::handle(release),
             AppArguments::Time { day, all, store } => time::handle(day, all, store),
-            AppArguments::Download { day } => download::handle(day),
+            AppArguments::WatchDay { day, features } => watch::handle(day, features.as_deref()),
+            AppArguments::Download { day, force } => download::handle(day, force),
             AppArguments::Read { day } => read::handle(day),
-            AppArguments::Scaffold { day, download, overwrite } => {
-                scaffold::handle(day, overwrite);
+            AppArguments::Example { day, yes } => example::handle(day, yes),
+            AppArguments::Scaffold {
+                day,
+                download,
+                overwrite,
+                template,
+            } => {
+                // download first, the template uses the title of the puzzle.
+                if download {
+                    download::handle(day, false);
+                }
+                scaffold::handle(day, overwrite, &template);
                 if download {
-                    download::handle(day);
+                    example::handle(day, false);
                 }
             }
             AppArguments::Solve {
@@ -117,13 +196,17 @@ fn main() {
                 release,
                 dhat,
                 submit,
-            } => solve::handle(day, release, dhat, submit),
+                visualize,
+                debug,
+                input,
+            } => solve::handle(day, release, dhat, submit, &visualize, &debug, &input),
             #[cfg(feature = "today")]
             AppArguments::Today => {
                 match Day::today() {
                     Some(day) => {
-                        scaffold::handle(day, false);
-                        download::handle(day);
+                        download::handle(day, false);
+                        scaffold::handle(day, false, scaffold::DEFAULT_TEMPLATE);
+                        example::handle(day, false);
                         read::handle(day)
                     }
                     None => {
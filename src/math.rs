@@ -0,0 +1,159 @@
+/// Greatest common divisor, always non-negative.
+pub fn gcd(a: i64, b: i64) -> i64 {
+    let (mut a, mut b) = (a.abs(), b.abs());
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// Least common multiple, always non-negative. `lcm(0, n)` is 0.
+pub fn lcm(a: i64, b: i64) -> i64 {
+    if a == 0 || b == 0 {
+        return 0;
+    }
+    (a / gcd(a, b) * b).abs()
+}
+
+/// Returns `(g, x, y)` such that `a * x + b * y = g`, where `g` is the non-negative gcd.
+pub fn ext_gcd(a: i64, b: i64) -> (i64, i64, i64) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (1, 0);
+    let (mut old_y, mut y) = (0, 1);
+
+    while r != 0 {
+        let quotient = old_r / r;
+        (old_r, r) = (r, old_r - quotient * r);
+        (old_x, x) = (x, old_x - quotient * x);
+        (old_y, y) = (y, old_y - quotient * y);
+    }
+
+    if old_r < 0 {
+        (-old_r, -old_x, -old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+/// `base^exp mod modulus`, in `0..modulus`. The modulus must be positive, and any one that fits
+/// in an `i64` is safe from overflow, since the products are computed in `i128`.
+pub fn mod_pow(base: i64, mut exp: u64, modulus: i64) -> i64 {
+    let modulus = i128::from(modulus);
+    let mut base = i128::from(base).rem_euclid(modulus);
+    let mut result = 1 % modulus;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = result * base % modulus;
+        }
+        base = base * base % modulus;
+        exp >>= 1;
+    }
+    result as i64
+}
+
+/// The inverse of `a` modulo `modulus`, in `0..modulus`, if `a` and `modulus` are coprime.
+pub fn mod_inv(a: i64, modulus: i64) -> Option<i64> {
+    let (g, x, _) = ext_gcd(a.rem_euclid(modulus), modulus);
+    (g == 1).then(|| x.rem_euclid(modulus))
+}
+
+/// Solves `x = r (mod m)` for every `(r, m)` with the Chinese remainder theorem.
+///
+/// Moduli must be positive but do not need to be coprime. Returns `(x, lcm)`, the smallest
+/// non-negative solution and the least common multiple of the moduli. Returns `None` if the
+/// congruences contradict each other, if a modulus is not positive, or if the least common
+/// multiple does not fit in an `i64`.
+pub fn crt(congruences: &[(i64, i64)]) -> Option<(i64, i64)> {
+    let mut solution: i128 = 0;
+    let mut modulus: i128 = 1;
+
+    for &(remainder, m) in congruences {
+        if m <= 0 {
+            return None;
+        }
+
+        let (g, inv, _) = ext_gcd((modulus % i128::from(m)) as i64, m);
+        let difference = i128::from(remainder) - solution;
+        if difference % i128::from(g) != 0 {
+            return None;
+        }
+
+        // solution + modulus * k = remainder (mod m), so k = difference / g * inv (mod m / g).
+        let step = i128::from(m / g);
+        let k = (difference / i128::from(g) * i128::from(inv)).rem_euclid(step);
+        solution += modulus * k;
+        modulus *= step;
+        if modulus > i128::from(i64::MAX) {
+            return None;
+        }
+        solution = solution.rem_euclid(modulus);
+    }
+
+    // the solution is below the modulus, so it fits too.
+    Some((solution as i64, modulus as i64))
+}
+
+/// Number of decimal digits of `n`, 1 for 0.
+pub fn num_digits(n: u64) -> u32 {
+    n.checked_ilog10().unwrap_or(0) + 1
+}
+
+/// Splits `n` before its `low_digits` last decimal digits, e.g. `(12, 345)` for `(12345, 3)`.
+pub fn split_digits(n: u64, low_digits: u32) -> (u64, u64) {
+    let divisor = 10u64.pow(low_digits);
+    (n / divisor, n % divisor)
+}
+
+/// Writes the digits of `b` after the ones of `a`, e.g. 12345 for 12 and 345.
+/// Returns `None` on overflow.
+pub fn concat(a: u64, b: u64) -> Option<u64> {
+    a.checked_mul(10u64.pow(num_digits(b)))?.checked_add(b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn computes_modular_arithmetic() {
+        assert_eq!(gcd(-12, 18), 6);
+        assert_eq!(lcm(4, -6), 12);
+        assert_eq!(lcm(101, 103), 10403);
+
+        let (g, x, y) = ext_gcd(240, 46);
+        assert_eq!((g, 240 * x + 46 * y), (2, 2));
+
+        assert_eq!(mod_pow(3, 200, 13), 9);
+        assert_eq!(mod_pow(-2, 3, 5), 2);
+        assert_eq!(mod_inv(3, 11), Some(4));
+        assert_eq!(mod_inv(4, 10), None);
+    }
+
+    #[test]
+    fn solves_congruences() {
+        assert_eq!(crt(&[(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        // not coprime, but compatible.
+        assert_eq!(crt(&[(2, 4), (4, 6)]), Some((10, 12)));
+        assert_eq!(crt(&[(1, 4), (2, 6)]), None);
+        assert_eq!(crt(&[]), Some((0, 1)));
+        assert_eq!(crt(&[(1, 0)]), None);
+        assert_eq!(crt(&[(1, -3)]), None);
+
+        let large_prime = 4_294_967_311;
+        assert_eq!(crt(&[(1, large_prime), (2, 4_294_967_357)]), None);
+        assert_eq!(crt(&[(1, large_prime), (1, 2)]), Some((1, 2 * large_prime)));
+    }
+
+    #[test]
+    fn handles_digits() {
+        assert_eq!(num_digits(0), 1);
+        assert_eq!(num_digits(9), 1);
+        assert_eq!(num_digits(10), 2);
+        assert_eq!(num_digits(u64::MAX), 20);
+        assert_eq!(split_digits(253000, 3), (253, 0));
+        assert_eq!(split_digits(17, 1), (1, 7));
+        assert_eq!(concat(12, 345), Some(12345));
+        assert_eq!(concat(15, 0), Some(150));
+        assert_eq!(concat(u64::MAX, 1), None);
+    }
+}
@@ -0,0 +1,153 @@
+/// Memoization of recursive functions, replacing caches threaded by hand through every call.
+/// Solutions can print the [`Stats`] of their caches when run with `--debug-stats`, see
+/// [`crate::debug`].
+use std::{collections::HashMap, fmt::Display, hash::Hash};
+
+/// How often a [`Memo`] found a value in its cache.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Stats {
+    pub hits: usize,
+    pub misses: usize,
+}
+
+impl Stats {
+    /// The share of lookups answered by the cache, 0 without lookups.
+    pub fn hit_rate(&self) -> f64 {
+        let lookups = self.hits + self.misses;
+        if lookups == 0 {
+            0.
+        } else {
+            self.hits as f64 / lookups as f64
+        }
+    }
+}
+
+impl Display for Stats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} hits, {} misses ({:.1}% hit rate)",
+            self.hits,
+            self.misses,
+            self.hit_rate() * 100.
+        )
+    }
+}
+
+/// A cache of the values of a recursive function. The computation given to
+/// [`Memo::get_or_compute`] receives the memo back, so it can recurse through it:
+///
+/// ```
+/// use advent_of_code::memoize::Memo;
+///
+/// fn fibonacci(memo: &mut Memo<u64, u64>, n: u64) -> u64 {
+///     if n < 2 {
+///         return n;
+///     }
+///     memo.get_or_compute(n, |memo| fibonacci(memo, n - 1) + fibonacci(memo, n - 2))
+/// }
+///
+/// let mut memo = Memo::new();
+/// assert_eq!(fibonacci(&mut memo, 90), 2880067194370816120);
+/// assert_eq!(memo.stats().misses, 89);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Memo<K, V> {
+    cache: HashMap<K, V>,
+    stats: Stats,
+}
+
+impl<K, V> Default for Memo<K, V> {
+    fn default() -> Self {
+        Memo {
+            cache: HashMap::new(),
+            stats: Stats::default(),
+        }
+    }
+}
+
+impl<K: Eq + Hash, V: Clone> Memo<K, V> {
+    pub fn new() -> Memo<K, V> {
+        Memo::default()
+    }
+
+    /// Returns the cached value of `key`, or computes and caches it. The computation gets the
+    /// memo, to look up the values it depends on.
+    pub fn get_or_compute(&mut self, key: K, compute: impl FnOnce(&mut Self) -> V) -> V {
+        if let Some(value) = self.cache.get(&key) {
+            self.stats.hits += 1;
+            return value.clone();
+        }
+
+        self.stats.misses += 1;
+        let value = compute(self);
+        self.cache.insert(key, value.clone());
+        value
+    }
+
+    /// Returns the cached value of `key`, without counting a lookup.
+    pub fn get(&self, key: &K) -> Option<&V> {
+        self.cache.get(key)
+    }
+
+    pub fn stats(&self) -> Stats {
+        self.stats
+    }
+
+    pub fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+
+    /// Empties the cache and resets the statistics.
+    pub fn clear(&mut self) {
+        self.cache.clear();
+        self.stats = Stats::default();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // number of paths from (0, 0) to (x, y), moving right or down.
+    fn paths(memo: &mut Memo<(u32, u32), u64>, x: u32, y: u32) -> u64 {
+        if x == 0 || y == 0 {
+            return 1;
+        }
+        memo.get_or_compute((x, y), |memo| paths(memo, x - 1, y) + paths(memo, x, y - 1))
+    }
+
+    #[test]
+    fn caches_recursive_calls() {
+        let mut memo = Memo::new();
+        assert_eq!(paths(&mut memo, 16, 16), 601080390);
+
+        // each inner point is computed once, and looked up again from its other neighbor.
+        assert_eq!(memo.len(), 256);
+        assert_eq!(
+            memo.stats(),
+            Stats {
+                hits: 225,
+                misses: 256
+            }
+        );
+        assert_eq!(memo.get(&(1, 1)), Some(&2));
+
+        paths(&mut memo, 16, 16);
+        assert_eq!(memo.stats().hits, 226);
+
+        memo.clear();
+        assert!(memo.is_empty());
+        assert_eq!(memo.stats().hit_rate(), 0.);
+    }
+
+    #[test]
+    fn displays_stats() {
+        let stats = Stats { hits: 3, misses: 1 };
+        assert_eq!(stats.to_string(), "3 hits, 1 misses (75.0% hit rate)");
+    }
+}
@@ -0,0 +1,224 @@
+/// Partial orders given as `a` before `b` rules, such as the page ordering rules of day 5.
+///
+/// Rules are indexed by element, so checking or sorting a sequence does not scan every rule.
+use std::{
+    cmp::Ordering,
+    collections::{HashMap, HashSet, VecDeque},
+    error::Error,
+    fmt::{Debug, Display},
+    hash::Hash,
+};
+
+/// Rules that form a cycle, so no order satisfies them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CycleError<T> {
+    /// The rules along the cycle, each one starting where the previous one ends.
+    pub rules: Vec<(T, T)>,
+}
+
+impl<T: Display> Display for CycleError<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let rules: Vec<String> = self.rules.iter().map(|(a, b)| format!("{a}|{b}")).collect();
+        write!(f, "the rules form a cycle: {}", rules.join(", "))
+    }
+}
+
+impl<T: Debug + Display> Error for CycleError<T> {}
+
+#[derive(Debug, Clone)]
+pub struct Rules<T> {
+    /// The elements that must come after each element.
+    successors: HashMap<T, HashSet<T>>,
+}
+
+impl<T> Default for Rules<T> {
+    fn default() -> Self {
+        Rules {
+            successors: HashMap::new(),
+        }
+    }
+}
+
+impl<T: Copy + Eq + Hash> Rules<T> {
+    pub fn new() -> Rules<T> {
+        Rules::default()
+    }
+
+    /// Adds the rule that `before` comes before `after`.
+    pub fn add(&mut self, before: T, after: T) {
+        self.successors.entry(before).or_default().insert(after);
+    }
+
+    /// Whether a rule puts `before` before `after`.
+    pub fn precedes(&self, before: &T, after: &T) -> bool {
+        self.successors
+            .get(before)
+            .is_some_and(|successors| successors.contains(after))
+    }
+
+    /// Returns a rule broken by the sequence, if any. Among the broken rules, returns the one
+    /// whose `after` element comes first in the sequence.
+    pub fn violation(&self, sequence: &[T]) -> Option<(T, T)> {
+        let positions: HashMap<T, usize> = sequence
+            .iter()
+            .enumerate()
+            .map(|(idx, element)| (*element, idx))
+            .collect();
+
+        sequence
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, element)| {
+                let successors = self.successors.get(element)?;
+                let earliest = successors
+                    .iter()
+                    .filter_map(|after| positions.get(after).filter(|pos| **pos < idx))
+                    .min()?;
+                Some((*earliest, (*element, sequence[*earliest])))
+            })
+            .min_by_key(|(earliest, _)| *earliest)
+            .map(|(_, rule)| rule)
+    }
+
+    pub fn is_ordered(&self, sequence: &[T]) -> bool {
+        self.violation(sequence).is_none()
+    }
+
+    /// Orders two elements by the rule between them, [`Ordering::Equal`] without rule.
+    /// This is only a total order, usable with [`slice::sort_by`], if every pair of the sorted
+    /// elements has a rule. Otherwise, use [`Rules::topological_sort`].
+    pub fn compare(&self, a: &T, b: &T) -> Ordering {
+        if self.precedes(a, b) {
+            Ordering::Less
+        } else if self.precedes(b, a) {
+            Ordering::Greater
+        } else {
+            Ordering::Equal
+        }
+    }
+
+    /// Sorts distinct elements with Kahn's algorithm, only considering the rules between them.
+    /// Elements that are not constrained keep their relative order.
+    pub fn topological_sort(&self, elements: &[T]) -> Result<Vec<T>, CycleError<T>> {
+        let subset: HashSet<T> = elements.iter().copied().collect();
+        let successors_in_subset = |element: &T| -> Vec<T> {
+            self.successors
+                .get(element)
+                .map(|successors| {
+                    elements
+                        .iter()
+                        .filter(|e| successors.contains(e))
+                        .copied()
+                        .collect()
+                })
+                .unwrap_or_default()
+        };
+
+        let mut pending: HashMap<T, usize> = subset.iter().map(|e| (*e, 0)).collect();
+        for element in &subset {
+            for after in successors_in_subset(element) {
+                *pending.get_mut(&after).unwrap() += 1;
+            }
+        }
+
+        let mut queue: VecDeque<T> = elements
+            .iter()
+            .filter(|e| pending[e] == 0)
+            .copied()
+            .collect();
+
+        let mut sorted = Vec::with_capacity(subset.len());
+        while let Some(element) = queue.pop_front() {
+            sorted.push(element);
+            for after in successors_in_subset(&element) {
+                let count = pending.get_mut(&after).unwrap();
+                *count -= 1;
+                if *count == 0 {
+                    queue.push_back(after);
+                }
+            }
+        }
+
+        if sorted.len() < subset.len() {
+            let stuck: HashSet<T> = subset.into_iter().filter(|e| pending[e] > 0).collect();
+            return Err(self.find_cycle(&stuck));
+        }
+        Ok(sorted)
+    }
+
+    /// Follows rules between elements left out of the sort until an element repeats.
+    /// Every stuck element has a stuck predecessor, so the walk backwards never ends early.
+    fn find_cycle(&self, stuck: &HashSet<T>) -> CycleError<T> {
+        let predecessor = |element: &T| {
+            *stuck
+                .iter()
+                .find(|before| self.precedes(before, element))
+                .unwrap()
+        };
+
+        let mut walk = vec![*stuck.iter().next().unwrap()];
+        loop {
+            let before = predecessor(walk.last().unwrap());
+            if let Some(start) = walk.iter().position(|e| *e == before) {
+                let mut cycle: Vec<T> = walk[start..].to_vec();
+                cycle.push(before);
+                cycle.reverse();
+                return CycleError {
+                    rules: cycle.windows(2).map(|pair| (pair[0], pair[1])).collect(),
+                };
+            }
+            walk.push(before);
+        }
+    }
+}
+
+impl<T: Copy + Eq + Hash> FromIterator<(T, T)> for Rules<T> {
+    fn from_iter<I: IntoIterator<Item = (T, T)>>(iter: I) -> Self {
+        let mut rules = Rules::new();
+        for (before, after) in iter {
+            rules.add(before, after);
+        }
+        rules
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checks_and_sorts_sequences() {
+        let rules: Rules<u32> = [
+            (47, 53),
+            (97, 13),
+            (97, 61),
+            (75, 47),
+            (61, 53),
+            (97, 47),
+            (75, 53),
+        ]
+        .into_iter()
+        .collect();
+
+        assert!(rules.is_ordered(&[75, 47, 61, 53]));
+        assert_eq!(rules.violation(&[61, 13, 97]), Some((97, 61)));
+        assert_eq!(rules.topological_sort(&[61, 13, 97]), Ok(vec![97, 61, 13]));
+
+        let mut pages = vec![53, 47, 75];
+        pages.sort_by(|a, b| rules.compare(a, b));
+        assert_eq!(pages, vec![75, 47, 53]);
+    }
+
+    #[test]
+    fn reports_cycles() {
+        let rules: Rules<u32> = [(1, 2), (2, 3), (3, 1), (3, 4)].into_iter().collect();
+
+        let cycle = rules.topological_sort(&[4, 3, 2, 1]).unwrap_err();
+        assert_eq!(cycle.rules.len(), 3);
+        assert!(cycle.rules.windows(2).all(|pair| pair[0].1 == pair[1].0));
+        assert_eq!(cycle.rules.last().unwrap().1, cycle.rules[0].0);
+
+        // without 1, the rules between the elements have no cycle.
+        assert_eq!(rules.topological_sort(&[4, 3, 2]), Ok(vec![2, 3, 4]));
+    }
+}
@@ -0,0 +1,357 @@
+/// Parsing helpers for the usual puzzle input formats.
+///
+/// Every helper returns a [`ParseError`] pointing at the offending line and column
+/// instead of panicking, so a malformed input is reported instead of crashing the solution.
+use std::{any::type_name, error::Error, fmt::Display, str::FromStr};
+
+use crate::Map2D;
+
+/// An error with the position (1-based) of the input that could not be parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl ParseError {
+    pub fn new(line: usize, column: usize, message: impl Into<String>) -> ParseError {
+        ParseError {
+            line,
+            column,
+            message: message.into(),
+        }
+    }
+
+    /// Moves the error to another line, for helpers that only see a single line.
+    #[must_use]
+    pub fn at_line(mut self, line: usize) -> ParseError {
+        self.line = line;
+        self
+    }
+
+    /// Shifts the error by the number of lines preceding a block.
+    #[must_use]
+    pub fn offset_lines(mut self, offset: usize) -> ParseError {
+        self.line += offset;
+        self
+    }
+}
+
+impl Error for ParseError {}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.message
+        )
+    }
+}
+
+/// Returns the 1-based column of a byte offset in `line`.
+fn column_of(line: &str, offset: usize) -> usize {
+    line[..offset].chars().count() + 1
+}
+
+fn parse_value<T: FromStr>(line: &str, start: usize, value: &str) -> Result<T, ParseError> {
+    value.trim().parse().map_err(|_| {
+        ParseError::new(
+            1,
+            column_of(line, start),
+            format!("could not parse `{value}` as {}", type_name::<T>()),
+        )
+    })
+}
+
+/// Extracts every integer of a line, ignoring anything else. A `-` directly followed by a digit
+/// is a sign, unless it comes right after another digit (e.g. `1-3` is `1` and `3`).
+pub fn ints<T: FromStr>(line: &str) -> Result<Vec<T>, ParseError> {
+    let bytes = line.as_bytes();
+    let mut values = vec![];
+    let mut idx = 0;
+
+    while idx < bytes.len() {
+        let is_sign = bytes[idx] == b'-'
+            && bytes.get(idx + 1).is_some_and(u8::is_ascii_digit)
+            && (idx == 0 || !bytes[idx - 1].is_ascii_digit());
+
+        if !is_sign && !bytes[idx].is_ascii_digit() {
+            idx += 1;
+            continue;
+        }
+
+        let start = idx;
+        idx += 1;
+        while idx < bytes.len() && bytes[idx].is_ascii_digit() {
+            idx += 1;
+        }
+        values.push(parse_value(line, start, &line[start..idx])?);
+    }
+
+    Ok(values)
+}
+
+/// A group of consecutive non-blank lines.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Block<'a> {
+    /// Number of the first line of the block in the whole input (1-based).
+    pub line: usize,
+    pub text: &'a str,
+}
+
+/// Splits the input on blank lines.
+pub fn blocks(input: &str) -> Vec<Block<'_>> {
+    let mut blocks = vec![];
+    let mut start: Option<(usize, usize)> = None;
+    let mut offset = 0;
+
+    for (idx, line) in input.split_inclusive('\n').enumerate() {
+        let is_blank = line.trim().is_empty();
+        match (start, is_blank) {
+            (None, false) => start = Some((idx + 1, offset)),
+            (Some((line_nb, begin)), true) => {
+                blocks.push(Block {
+                    line: line_nb,
+                    text: input[begin..offset].trim_end(),
+                });
+                start = None;
+            }
+            _ => {}
+        }
+        offset += line.len();
+    }
+
+    if let Some((line_nb, begin)) = start {
+        blocks.push(Block {
+            line: line_nb,
+            text: input[begin..].trim_end(),
+        });
+    }
+
+    blocks
+}
+
+/// Applies `parse` to every line, fixing the line number of the errors it returns.
+pub fn lines_with<T>(
+    input: &str,
+    parse: impl Fn(&str) -> Result<T, ParseError>,
+) -> Result<Vec<T>, ParseError> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(idx, line)| parse(line).map_err(|e| e.at_line(idx + 1)))
+        .collect()
+}
+
+/// Parses a `key: value` line, both sides are trimmed.
+pub fn key_value(line: &str) -> Result<(&str, &str), ParseError> {
+    line.split_once(':')
+        .map(|(key, value)| (key.trim(), value.trim()))
+        .ok_or_else(|| ParseError::new(1, 1, format!("expected `key: value`, got `{line}`")))
+}
+
+/// Parses every non-blank line as a `key: value` pair.
+pub fn key_values(input: &str) -> Result<Vec<(&str, &str)>, ParseError> {
+    input
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(idx, line)| key_value(line).map_err(|e| e.at_line(idx + 1)))
+        .collect()
+}
+
+/// Parses a rectangular grid of characters, converting each one with `tile`.
+/// Characters for which `tile` returns [`None`] are reported as errors.
+pub fn grid<T>(input: &str, tile: impl Fn(char) -> Option<T>) -> Result<Map2D<T>, ParseError> {
+    let width = input.lines().next().map_or(0, |line| line.chars().count());
+    let mut tiles = vec![];
+
+    for (y, line) in input.lines().enumerate() {
+        let line_width = line.chars().count();
+        if line_width != width {
+            return Err(ParseError::new(
+                y + 1,
+                line_width.min(width) + 1,
+                format!("expected a row of {width} tiles, got {line_width}"),
+            ));
+        }
+
+        for (x, c) in line.chars().enumerate() {
+            let value = tile(c)
+                .ok_or_else(|| ParseError::new(y + 1, x + 1, format!("unexpected tile `{c}`")))?;
+            tiles.push(value);
+        }
+    }
+
+    Ok(Map2D {
+        width,
+        height: input.lines().count(),
+        tiles,
+    })
+}
+
+/// Parses a rectangular grid of characters as is.
+pub fn char_grid(input: &str) -> Result<Map2D<char>, ParseError> {
+    grid(input, Some)
+}
+
+/// A scanf-like pattern where each `{}` captures a value, e.g. `"Button A: X+{}, Y+{}"`.
+#[derive(Debug, Clone)]
+pub struct Pattern<'p> {
+    literals: Vec<&'p str>,
+}
+
+impl<'p> Pattern<'p> {
+    pub fn new(pattern: &'p str) -> Pattern<'p> {
+        Pattern {
+            literals: pattern.split("{}").collect(),
+        }
+    }
+
+    /// Returns the raw text of each placeholder, along with its byte offset in `line`.
+    fn capture_offsets<'s>(&self, line: &'s str) -> Result<Vec<(usize, &'s str)>, ParseError> {
+        let expected = |offset: usize, literal: &str| {
+            ParseError::new(1, column_of(line, offset), format!("expected `{literal}`"))
+        };
+
+        let first = self.literals[0];
+        if !line.starts_with(first) {
+            return Err(expected(0, first));
+        }
+        // without placeholder, the literal is the whole line.
+        if self.literals.len() == 1 && line.len() > first.len() {
+            return Err(ParseError::new(
+                1,
+                column_of(line, first.len()),
+                "expected the end of the line",
+            ));
+        }
+
+        let mut captures = vec![];
+        let mut offset = first.len();
+        let last_idx = self.literals.len() - 1;
+
+        for (idx, literal) in self.literals.iter().enumerate().skip(1) {
+            let rest = &line[offset..];
+            let end = if idx == last_idx {
+                // the trailing literal must end the line.
+                rest.strip_suffix(literal)
+                    .map(str::len)
+                    .ok_or_else(|| expected(line.len(), literal))?
+            } else if literal.is_empty() {
+                return Err(ParseError::new(
+                    1,
+                    column_of(line, offset),
+                    "pattern has two consecutive placeholders",
+                ));
+            } else {
+                rest.find(literal)
+                    .ok_or_else(|| expected(offset, literal))?
+            };
+
+            captures.push((offset, &rest[..end]));
+            offset += end + literal.len();
+        }
+
+        Ok(captures)
+    }
+
+    /// Returns the raw text of each placeholder.
+    pub fn captures<'s>(&self, line: &'s str) -> Result<Vec<&'s str>, ParseError> {
+        Ok(self
+            .capture_offsets(line)?
+            .into_iter()
+            .map(|(_, capture)| capture)
+            .collect())
+    }
+
+    /// Returns each placeholder parsed as `T`.
+    pub fn parse<T: FromStr>(&self, line: &str) -> Result<Vec<T>, ParseError> {
+        self.capture_offsets(line)?
+            .into_iter()
+            .map(|(offset, capture)| parse_value(line, offset, capture))
+            .collect()
+    }
+}
+
+/// Matches `line` against `pattern` and parses every placeholder as `T`.
+pub fn scan<T: FromStr>(pattern: &str, line: &str) -> Result<Vec<T>, ParseError> {
+    Pattern::new(pattern).parse(line)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extracts_signed_ints() {
+        assert_eq!(ints::<i32>("p=0,4 v=3,-3").unwrap(), vec![0, 4, 3, -3]);
+        assert_eq!(ints::<i32>("1-3 a: -5").unwrap(), vec![1, 3, -5]);
+        assert_eq!(
+            ints::<u8>("a 300").unwrap_err(),
+            ParseError::new(1, 3, "could not parse `300` as u8")
+        );
+    }
+
+    #[test]
+    fn splits_blocks_with_line_numbers() {
+        let blocks = blocks("a\nb\n\n\nc\n");
+
+        assert_eq!(blocks.len(), 2);
+        assert_eq!(
+            blocks[0],
+            Block {
+                line: 1,
+                text: "a\nb"
+            }
+        );
+        assert_eq!(blocks[1], Block { line: 5, text: "c" });
+    }
+
+    #[test]
+    fn scans_patterns() {
+        let button = Pattern::new("Button A: X+{}, Y+{}");
+
+        assert_eq!(
+            button.parse::<u64>("Button A: X+94, Y+34").unwrap(),
+            [94, 34]
+        );
+        assert_eq!(
+            button.parse::<u64>("Button A: X+94; Y+34").unwrap_err(),
+            ParseError::new(1, 13, "expected `, Y+`")
+        );
+        assert_eq!(
+            scan::<String>("{} -> {}", "x00 -> z01").unwrap(),
+            ["x00", "z01"]
+        );
+
+        let literal = Pattern::new("abc");
+        assert_eq!(literal.captures("abc").unwrap(), Vec::<&str>::new());
+        assert_eq!(
+            literal.captures("abcdef").unwrap_err(),
+            ParseError::new(1, 4, "expected the end of the line")
+        );
+    }
+
+    #[test]
+    fn reports_grid_errors() {
+        assert_eq!(char_grid("ab\ncd").unwrap().tiles, ['a', 'b', 'c', 'd']);
+        assert_eq!(
+            char_grid("ab\nc").unwrap_err(),
+            ParseError::new(2, 2, "expected a row of 2 tiles, got 1")
+        );
+        assert_eq!(
+            grid("01\n2x", |c| c.to_digit(10)).unwrap_err(),
+            ParseError::new(2, 2, "unexpected tile `x`")
+        );
+    }
+
+    #[test]
+    fn parses_key_values() {
+        let pairs = key_values("Register A: 729\n\nProgram: 0,1").unwrap();
+        assert_eq!(pairs, [("Register A", "729"), ("Program", "0,1")]);
+        assert_eq!(key_values("a: 1\nb").unwrap_err().line, 2);
+    }
+}
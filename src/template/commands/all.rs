@@ -1,5 +1,11 @@
+use std::process;
+
 use crate::template::{all_days, run_multi::run_multi};
 
 pub fn handle(is_release: bool) {
-    run_multi(&all_days().collect(), is_release, false);
+    let run = run_multi(&all_days().collect(), is_release, false);
+
+    if !run.failed_days.is_empty() {
+        process::exit(1);
+    }
 }
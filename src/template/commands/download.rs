@@ -1,14 +1,75 @@
-use crate::template::{aoc_cli, Day};
-use std::process;
+use crate::template::integrity::{self, InputProblem, Manifest};
+use crate::template::{aoc_cli, paths, Day};
+use std::{fs, process};
 
-pub fn handle(day: Day) {
+/// Downloads the input and the puzzle of a day.
+/// A valid input is kept unless `force` is set, only the puzzle is downloaded again.
+pub fn handle(day: Day, force: bool) {
     if aoc_cli::check().is_err() {
         eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
         process::exit(1);
     }
 
+    if !force {
+        let existing = match integrity::check_input(day) {
+            Ok(()) => Some("is already downloaded"),
+            Err(InputProblem::ChecksumMismatch) => Some("was modified locally"),
+            Err(_) => None,
+        };
+
+        if let Some(reason) = existing {
+            println!(
+                "Input of day {day} {reason}, skipping it. Use `--force` to download it again."
+            );
+            if let Err(e) = aoc_cli::download_puzzle(day) {
+                eprintln!("failed to call aoc-cli: {e}");
+                process::exit(1);
+            }
+            return;
+        }
+    }
+
     if let Err(e) = aoc_cli::download(day) {
         eprintln!("failed to call aoc-cli: {e}");
         process::exit(1);
     };
+
+    let input_path = paths::input_file(day);
+    let content = fs::read_to_string(&input_path).unwrap_or_default();
+
+    if let Err(problem) = integrity::check_content(&content) {
+        // do not leave garbage behind, solutions would parse it as their input.
+        let _ = fs::remove_file(&input_path);
+        eprintln!(
+            "Downloaded input is invalid: {problem}. Removed \"{}\".",
+            input_path.display()
+        );
+        process::exit(1);
+    }
+
+    let mut manifest = Manifest::read();
+    manifest.insert(day, &content);
+    if let Err(e) = manifest.store() {
+        eprintln!("Failed to store the input checksum: {e}");
+    }
+
+    #[cfg(feature = "encrypted-inputs")]
+    encrypt_input(&input_path, &content);
+}
+
+/// Replaces the plain input with its encrypted form, which can be committed.
+#[cfg(feature = "encrypted-inputs")]
+fn encrypt_input(input_path: &std::path::Path, content: &str) {
+    use crate::template::encryption;
+
+    match encryption::write(input_path, content) {
+        Ok(encrypted_path) => {
+            let _ = fs::remove_file(input_path);
+            println!("🎄 Encrypted input to \"{}\".", encrypted_path.display());
+        }
+        Err(e) => {
+            eprintln!("Failed to encrypt the input, kept it in plain text: {e}");
+            process::exit(1);
+        }
+    }
 }
@@ -0,0 +1,229 @@
+/// Fills the example files and the expected answers of the tests of a day from its puzzle
+/// description. Guesses that are not certain are only applied after confirmation.
+use std::{
+    fs,
+    io::{self, Write},
+    path::Path,
+    process,
+};
+
+use crate::template::{
+    aoc_cli,
+    commands::scaffold::format_expected,
+    paths,
+    puzzle::{self, Example},
+    Day, ANSI_BOLD, ANSI_RESET,
+};
+
+const PART_NAMES: [&str; 2] = ["one", "two"];
+
+fn confirm(question: &str) -> bool {
+    print!("{question} [y/N] ");
+    let _ = io::stdout().flush();
+
+    let mut answer = String::new();
+    io::stdin().read_line(&mut answer).is_ok()
+        && matches!(answer.trim().to_lowercase().as_str(), "y" | "yes")
+}
+
+/// Sets the expected value of the test of a part, if it is still `None`.
+/// With `part_file`, the test reads the example of the part (e.g. `NN-2.txt`).
+/// Returns [`None`] if the test was not found or already has an expected value.
+pub fn set_expected(source: &str, part: u8, answer: &str, part_file: bool) -> Option<String> {
+    let name = PART_NAMES[usize::from(part) - 1];
+    let test_start = source.find(&format!("fn test_part_{name}()"))?;
+    let assert_start = test_start + source[test_start..].find("assert_eq!(")?;
+    let assert_end = assert_start + source[assert_start..].find(';')?;
+
+    let assertion = &source[assert_start..assert_end];
+    if !assertion.contains("None") {
+        return None;
+    }
+
+    let mut test_body = source[test_start..assert_start].to_string();
+    if part_file {
+        test_body = test_body.replace(
+            "read_file(\"examples\", DAY)",
+            &format!("read_file_part(\"examples\", DAY, {part})"),
+        );
+    }
+
+    Some(format!(
+        "{}{test_body}{}{}",
+        &source[..test_start],
+        assertion.replacen("None", &format_expected(Some(answer)), 1),
+        &source[assert_end..]
+    ))
+}
+
+/// Writes an example file, asking before replacing a different non-empty file.
+fn write_example(path: &Path, input: &str, assume_yes: bool) -> bool {
+    let existing = fs::read_to_string(path).unwrap_or_default();
+    if existing == input {
+        return true;
+    }
+
+    let question = format!("\"{}\" already has an example, replace it?", path.display());
+    if !existing.trim().is_empty() && !assume_yes && !confirm(&question) {
+        return false;
+    }
+
+    match fs::write(path, input) {
+        Ok(()) => {
+            println!("Wrote example to \"{}\"", path.display());
+            true
+        }
+        Err(e) => {
+            eprintln!("Failed to write example file: {e}");
+            false
+        }
+    }
+}
+
+fn apply(day: Day, part: u8, example: &Example, part_one_input: Option<&str>, assume_yes: bool) {
+    // a part two example identical to the one of part one does not need its own file.
+    let input = example
+        .input
+        .as_deref()
+        .filter(|input| part == 1 || Some(*input) != part_one_input);
+
+    let part_file = part == 2 && input.is_some();
+    if let Some(input) = input {
+        let path = if part_file {
+            paths::data_file("examples", &format!("{day}-{part}.txt"))
+        } else {
+            paths::example_file(day)
+        };
+
+        if !write_example(&path, input, assume_yes) {
+            return;
+        }
+    }
+
+    let Some(answer) = &example.answer else {
+        return;
+    };
+
+    let module_path = paths::bin_file(day);
+    let Ok(source) = fs::read_to_string(&module_path) else {
+        eprintln!(
+            "Module \"{}\" not found, run `cargo scaffold {day}` first.",
+            module_path.display()
+        );
+        return;
+    };
+
+    match set_expected(&source, part, answer, part_file) {
+        Some(updated) => match fs::write(&module_path, updated) {
+            Ok(()) => println!("Part {part}: the test now expects {answer}."),
+            Err(e) => eprintln!("Failed to update module: {e}"),
+        },
+        None => println!("Part {part}: the test already has an expected value, left as is."),
+    }
+}
+
+/// Shows the guessed example of a part and applies it, after confirmation if unsure.
+fn propose(
+    day: Day,
+    part: u8,
+    example: Option<&Example>,
+    part_one_input: Option<&str>,
+    assume_yes: bool,
+) {
+    let Some(example) = example else {
+        println!("Part {part}: no example found.");
+        return;
+    };
+
+    println!("Part {part}:");
+    match &example.input {
+        Some(input) => println!("{input}"),
+        None => println!("(same example as part one)"),
+    }
+    match &example.answer {
+        Some(answer) => println!("Expected answer: {answer}"),
+        None => println!("Expected answer: not found"),
+    }
+
+    if !example.confident && !assume_yes && !confirm("Not sure about this example, use it?") {
+        return;
+    }
+
+    apply(day, part, example, part_one_input, assume_yes);
+}
+
+pub fn handle(day: Day, assume_yes: bool) {
+    let Some(markdown) = puzzle::read(day) else {
+        eprintln!("Puzzle of day {day} not found. Run `cargo download {day}` first.");
+        process::exit(1);
+    };
+
+    let [part_one, part_two] = puzzle::examples(&markdown);
+    let part_one_input = part_one.as_ref().and_then(|e| e.input.as_deref());
+
+    propose(day, 1, part_one.as_ref(), part_one_input, assume_yes);
+    propose(day, 2, part_two.as_ref(), part_one_input, assume_yes);
+}
+
+/// Called once part one is accepted: downloads the description of part two, prints it and
+/// extracts its example.
+pub fn handle_part_two(day: Day) {
+    if let Err(e) = aoc_cli::download_puzzle(day) {
+        eprintln!("Failed to download part two: {e}");
+        return;
+    }
+
+    let Some(markdown) = puzzle::read(day) else {
+        return;
+    };
+    let Some(description) = puzzle::part_two(&markdown) else {
+        eprintln!("The puzzle description has no part two yet.");
+        return;
+    };
+
+    println!();
+    println!("{ANSI_BOLD}{description}{ANSI_RESET}");
+    println!();
+
+    let [part_one, part_two] = puzzle::examples(&markdown);
+    let part_one_input = part_one.as_ref().and_then(|e| e.input.as_deref());
+    propose(day, 2, part_two.as_ref(), part_one_input, false);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MODULE: &str = r#"advent_of_code::solution!(1);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        assert_eq!(result, None);
+    }
+
+    #[test]
+    fn test_part_two() {
+        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        assert_eq!(result, None);
+    }
+}
+"#;
+
+    #[test]
+    fn sets_expected_answers() {
+        let updated = set_expected(MODULE, 1, "11", false).unwrap();
+        assert!(updated.contains("assert_eq!(result, Some(11));"));
+
+        let updated = set_expected(&updated, 2, "31", true).unwrap();
+        assert!(updated.contains("read_file_part(\"examples\", DAY, 2)"));
+        assert!(updated.contains("assert_eq!(result, Some(31));"));
+        assert_eq!(updated.matches("read_file(\"examples\", DAY)").count(), 1);
+
+        assert_eq!(set_expected(&updated, 1, "12", false), None);
+    }
+}
@@ -1,15 +1,60 @@
+/// Creates the module and example files of a day, the input is left to `download`.
+///
+/// The module is generated from a template: one of the built-in templates, or a `<name>.txt`
+/// file of the user template directory (see [`paths::templates_dir`]), which takes precedence.
+/// Templates can use the following placeholders:
+///  - `%DAY_NUMBER%`, `%DAY%` (zero-padded), `%YEAR%`,
+///  - `%TITLE%` and `%PUZZLE_URL%`, the title is only known once the puzzle is downloaded,
+///  - `%EXAMPLE_ANSWER_1%` and `%EXAMPLE_ANSWER_2%`, e.g. `Some(143)`, `None` when unknown.
+///
+/// When the registry module exists (in-process running), the day is added to it.
 use std::{
-    fs::{File, OpenOptions},
-    io::Write,
+    fs::{self, File, OpenOptions},
+    io::{ErrorKind, Write},
+    path::Path,
     process,
 };
 
-use crate::template::Day;
+use crate::template::{aoc_cli, paths, puzzle, Day};
 
-const MODULE_TEMPLATE: &str =
-    include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt"));
+pub const DEFAULT_TEMPLATE: &str = "default";
 
-fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error> {
+const BUILTIN_TEMPLATES: [(&str, &str); 4] = [
+    (
+        DEFAULT_TEMPLATE,
+        include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/src/templates/default.txt"
+        )),
+    ),
+    (
+        "grid",
+        include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/src/templates/grid.txt"
+        )),
+    ),
+    (
+        "graph",
+        include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/src/templates/graph.txt"
+        )),
+    ),
+    (
+        "puzzle-struct",
+        include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/src/templates/puzzle-struct.txt"
+        )),
+    ),
+];
+
+/// Lines of the registry module between which the days are listed.
+const REGISTRY_BEGIN: &str = "// scaffold:begin";
+const REGISTRY_END: &str = "// scaffold:end";
+
+fn safe_create_file(path: &Path, overwrite: bool) -> Result<File, std::io::Error> {
     let mut file = OpenOptions::new();
     if overwrite {
         file.create(true);
@@ -19,61 +64,187 @@ fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error>
     file.truncate(true).write(true).open(path)
 }
 
-fn create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new()
-        .write(true)
-        .create(true)
-        .truncate(true)
-        .open(path)
+/// Returns the template with the given name, user templates shadow the built-in ones.
+fn load_template(name: &str) -> Result<String, String> {
+    let user_template = paths::templates_dir().join(format!("{name}.txt"));
+    if user_template.exists() {
+        return fs::read_to_string(&user_template)
+            .map_err(|e| format!("could not read \"{}\": {e}", user_template.display()));
+    }
+
+    BUILTIN_TEMPLATES
+        .iter()
+        .find(|(template, _)| *template == name)
+        .map(|(_, content)| content.to_string())
+        .ok_or_else(|| {
+            let names: Vec<&str> = BUILTIN_TEMPLATES.iter().map(|(name, _)| *name).collect();
+            format!(
+                "unknown template `{name}`, expecting one of: {} or a file of \"{}\"",
+                names.join(", "),
+                paths::templates_dir().display()
+            )
+        })
 }
 
-pub fn handle(day: Day, overwrite: bool) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
-    let module_path = format!("src/bin/{day}.rs");
+/// Formats an example answer as the expected result of a test.
+pub fn format_expected(answer: Option<&str>) -> String {
+    match answer {
+        Some(answer) if answer.parse::<i64>().is_ok() => format!("Some({answer})"),
+        Some(answer) => format!("Some({answer:?}.to_string())"),
+        None => "None".into(),
+    }
+}
 
-    let mut file = match safe_create_file(&module_path, overwrite) {
-        Ok(file) => file,
-        Err(e) => {
-            eprintln!("Failed to create module file: {e}");
-            process::exit(1);
-        }
+fn render(template: &str, day: Day, answers: [Option<&str>; 2]) -> String {
+    let year = aoc_cli::get_year();
+    let title = puzzle::read(day)
+        .as_deref()
+        .and_then(puzzle::title)
+        .map_or_else(
+            || format!("Day {}", day.into_inner()),
+            |title| format!("Day {}: {title}", day.into_inner()),
+        );
+
+    template
+        .replace("%DAY_NUMBER%", &day.into_inner().to_string())
+        .replace("%DAY%", &day.to_string())
+        .replace("%YEAR%", &year.map(|y| y.to_string()).unwrap_or_default())
+        .replace("%TITLE%", &title)
+        .replace("%PUZZLE_URL%", &puzzle::url(year, day))
+        .replace("%EXAMPLE_ANSWER_1%", &format_expected(answers[0]))
+        .replace("%EXAMPLE_ANSWER_2%", &format_expected(answers[1]))
+}
+
+/// Inserts the module declaration of a day in the registry, keeping the days sorted.
+/// Returns [`None`] if the markers are missing.
+fn register_day(registry: &str, day: Day) -> Option<String> {
+    let entry = format!("#[path = \"bin/{day}.rs\"] pub mod day_{day};");
+    let begin = registry.find(REGISTRY_BEGIN)? + REGISTRY_BEGIN.len();
+    let end = begin + registry[begin..].find(REGISTRY_END)?;
+
+    let mut entries: Vec<&str> = registry[begin..end]
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect();
+    if !entries.contains(&entry.as_str()) {
+        entries.push(&entry);
+        entries.sort_unstable();
+    }
+
+    let indent = registry[..end]
+        .rsplit('\n')
+        .next()
+        .unwrap_or_default()
+        .to_string();
+    let listed: String = entries
+        .iter()
+        .map(|entry| format!("\n{indent}{entry}"))
+        .collect();
+
+    Some(format!(
+        "{}{listed}\n{indent}{}",
+        &registry[..begin],
+        &registry[end..]
+    ))
+}
+
+fn update_registry(day: Day) {
+    let path = paths::registry_file();
+    let Ok(registry) = fs::read_to_string(&path) else {
+        return;
     };
 
-    match file.write_all(
-        MODULE_TEMPLATE
-            .replace("%DAY_NUMBER%", &day.into_inner().to_string())
-            .as_bytes(),
-    ) {
-        Ok(()) => {
-            println!("Created module file \"{}\"", &module_path);
-        }
+    match register_day(&registry, day).map(|updated| fs::write(&path, updated)) {
+        Some(Ok(())) => println!("Registered day {day} in \"{}\"", path.display()),
+        Some(Err(e)) => eprintln!("Failed to update the registry: {e}"),
+        None => eprintln!(
+            "Could not register day {day}: \"{}\" has no `{REGISTRY_BEGIN}` / `{REGISTRY_END}` markers.",
+            path.display()
+        ),
+    }
+}
+
+/// Creates an empty data file, existing files are kept.
+fn create_data_file(path: &Path, kind: &str) {
+    let file = path
+        .parent()
+        .map_or(Ok(()), fs::create_dir_all)
+        .and_then(|()| OpenOptions::new().write(true).create_new(true).open(path));
+
+    match file {
+        Ok(_) => println!("Created empty {kind} file \"{}\"", path.display()),
+        Err(e) if e.kind() == ErrorKind::AlreadyExists => {}
         Err(e) => {
-            eprintln!("Failed to write module contents: {e}");
+            eprintln!("Failed to create {kind} file: {e}");
             process::exit(1);
         }
     }
+}
+
+pub fn handle(day: Day, overwrite: bool, template: &str) {
+    let example_path = paths::example_file(day);
+    let module_path = paths::bin_file(day);
 
-    match create_file(&input_path) {
-        Ok(_) => {
-            println!("Created empty input file \"{}\"", &input_path);
+    let template = match load_template(template) {
+        Ok(template) => template,
+        Err(e) => {
+            eprintln!("Failed to load template: {e}");
+            process::exit(1);
         }
+    };
+
+    let mut file = match safe_create_file(&module_path, overwrite) {
+        Ok(file) => file,
         Err(e) => {
-            eprintln!("Failed to create input file: {e}");
+            eprintln!("Failed to create module file: {e}");
             process::exit(1);
         }
-    }
+    };
 
-    match create_file(&example_path) {
-        Ok(_) => {
-            println!("Created empty example file \"{}\"", &example_path);
+    match file.write_all(render(&template, day, [None, None]).as_bytes()) {
+        Ok(()) => {
+            println!("Created module file \"{}\"", module_path.display());
         }
         Err(e) => {
-            eprintln!("Failed to create example file: {e}");
+            eprintln!("Failed to write module contents: {e}");
             process::exit(1);
         }
     }
 
+    // no empty input, it would hide an encrypted one and look like a download.
+    create_data_file(&example_path, "example");
+    update_registry(day);
+
     println!("---");
     println!("🎄 Type `cargo solve {day}` to run your solution.");
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day;
+
+    #[test]
+    fn registers_days_in_order() {
+        let registry = "mod days {\n    // scaffold:begin\n    #[path = \"bin/03.rs\"] pub mod day_03;\n    // scaffold:end\n}\n";
+        let updated = register_day(registry, day!(1)).unwrap();
+
+        assert_eq!(
+            updated,
+            "mod days {\n    // scaffold:begin\n    #[path = \"bin/01.rs\"] pub mod day_01;\n    #[path = \"bin/03.rs\"] pub mod day_03;\n    // scaffold:end\n}\n"
+        );
+        assert_eq!(register_day(&updated, day!(1)).unwrap(), updated);
+        assert_eq!(register_day("mod days {}", day!(1)), None);
+    }
+
+    #[test]
+    fn renders_placeholders() {
+        let rendered = render(
+            "%DAY_NUMBER% %DAY% %EXAMPLE_ANSWER_1% %EXAMPLE_ANSWER_2%",
+            day!(7),
+            [Some("3749"), Some("4,6,3")],
+        );
+        assert_eq!(rendered, "7 07 Some(3749) Some(\"4,6,3\".to_string())");
+    }
+}
@@ -1,8 +1,23 @@
 use std::process::{Command, Stdio};
 
+use crate::template::input::InputSource;
+use crate::template::integrity::{self, InputProblem};
 use crate::template::Day;
+use crate::{debug, viz};
+
+pub fn handle(
+    day: Day,
+    release: bool,
+    dhat: bool,
+    submit_part: Option<u8>,
+    visualize: &viz::Options,
+    debug: &debug::Options,
+    input: &InputSource,
+) {
+    if *input == InputSource::Puzzle {
+        warn_about_input(day);
+    }
 
-pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
     let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
 
     if dhat {
@@ -16,6 +31,18 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
         cmd_args.push("--release".to_string());
     }
 
+    if visualize
+        .export
+        .is_some_and(|format| format.needs_feature())
+    {
+        cmd_args.extend(["--features".to_string(), "image-export".to_string()]);
+    }
+
+    // the solution has to decrypt its input too.
+    if cfg!(feature = "encrypted-inputs") {
+        cmd_args.extend(["--features".to_string(), "encrypted-inputs".to_string()]);
+    }
+
     cmd_args.push("--".to_string());
 
     if let Some(submit_part) = submit_part {
@@ -23,6 +50,10 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
         cmd_args.push(submit_part.to_string());
     }
 
+    cmd_args.extend(visualize.to_args());
+    cmd_args.extend(debug.to_args());
+    cmd_args.extend(input.to_args());
+
     let mut cmd = Command::new("cargo")
         .args(&cmd_args)
         .stdout(Stdio::inherit())
@@ -32,3 +63,15 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
 
     cmd.wait().unwrap();
 }
+
+/// Warns about an input that cannot be right, the solution still runs.
+fn warn_about_input(day: Day) {
+    match integrity::check_input(day) {
+        Ok(()) | Err(InputProblem::Missing) => {}
+        Err(problem) => {
+            eprintln!(
+                "⚠ Warning: {problem}. Run `cargo download {day} --force` to fetch it again."
+            );
+        }
+    }
+}
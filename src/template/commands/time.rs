@@ -21,7 +21,7 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
         |day| HashSet::from([day]),
     );
 
-    let timings = run_multi(&days_to_run, true, true).unwrap();
+    let timings = run_multi(&days_to_run, true, true).timings.unwrap();
 
     if store {
         let merged_timings = stored_timings.merge(&timings);
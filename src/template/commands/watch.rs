@@ -0,0 +1,313 @@
+/// Re-runs the example tests and the real input of a day whenever its files change.
+///
+/// Files are polled for modifications, which avoids a dependency on a platform watcher and is
+/// fast enough for a handful of files.
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+    process::{Command, Output, Stdio},
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use crate::template::{paths, Day, ANSI_BOLD, ANSI_CLEAR_SCREEN, ANSI_ITALIC, ANSI_RESET};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+const COLUMN_WIDTH: usize = 60;
+
+/// Last modification time of each watched file, [`None`] if it does not exist.
+type Snapshot = BTreeMap<PathBuf, Option<SystemTime>>;
+
+/// Whether a file of the examples directory belongs to the day, e.g. `07.txt` or `07-2.txt`.
+fn is_example_of(day: Day, file_name: &str) -> bool {
+    file_name.starts_with(&day.to_string()) && file_name.ends_with(".txt")
+}
+
+/// Collects the Rust sources below `dir`, recursively.
+fn rust_files(dir: &Path, files: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for path in entries.filter_map(Result::ok).map(|entry| entry.path()) {
+        if path.is_dir() {
+            rust_files(&path, files);
+        } else if path.extension().is_some_and(|ext| ext == "rs") {
+            files.push(path);
+        }
+    }
+}
+
+/// Returns the files that trigger a run: every source of the crate and the examples of the day.
+fn watched_files(day: Day) -> Vec<PathBuf> {
+    let mut files = vec![];
+    rust_files(&paths::resolve("src"), &mut files);
+
+    let examples_dir = paths::data_dir().join("examples");
+    if let Ok(entries) = fs::read_dir(&examples_dir) {
+        files.extend(
+            entries
+                .filter_map(Result::ok)
+                .map(|entry| entry.path())
+                .filter(|path| {
+                    path.file_name()
+                        .and_then(|name| name.to_str())
+                        .is_some_and(|name| is_example_of(day, name))
+                }),
+        );
+    }
+
+    files
+}
+
+fn snapshot(day: Day) -> Snapshot {
+    watched_files(day)
+        .into_iter()
+        .map(|path| {
+            let modified = fs::metadata(&path).and_then(|m| m.modified()).ok();
+            (path, modified)
+        })
+        .collect()
+}
+
+/// Returns the files added, removed or modified between two snapshots.
+fn changed_files(last: &Snapshot, current: &Snapshot) -> Vec<PathBuf> {
+    let removed = last.keys().filter(|path| !current.contains_key(*path));
+    let added_or_modified = current
+        .iter()
+        .filter(|(path, modified)| last.get(*path) != Some(modified))
+        .map(|(path, _)| path);
+
+    let mut changed: Vec<PathBuf> = removed.chain(added_or_modified).cloned().collect();
+    changed.sort_unstable();
+    changed
+}
+
+fn run_cargo(args: &[&str]) -> Option<Output> {
+    Command::new("cargo")
+        .args(args)
+        .stdin(Stdio::null())
+        .output()
+        .map_err(|e| eprintln!("Failed to run cargo: {e}"))
+        .ok()
+}
+
+/// Keeps the outcome of each test and the values of failed assertions.
+fn summarize_tests(stdout: &str, stderr: &str) -> Vec<String> {
+    if !stdout.contains("test result:") {
+        // the build failed, show the compiler errors.
+        return stderr
+            .lines()
+            .skip_while(|line| !line.starts_with("error"))
+            .map(str::to_string)
+            .collect();
+    }
+
+    stdout
+        .lines()
+        .filter_map(|line| {
+            let trimmed = line.trim();
+            if let Some(test) = trimmed.strip_prefix("test tests::") {
+                let (name, outcome) = test.split_once(" ... ")?;
+                let marker = if outcome == "ok" { "✔" } else { "✖" };
+                Some(format!("{marker} {name}"))
+            } else if trimmed.starts_with("left:") || trimmed.starts_with("right:") {
+                Some(format!("    {trimmed}"))
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+/// Keeps the lines printed by the solution, without the intermediate results.
+fn summarize_run(stdout: &str, stderr: &str, success: bool) -> Vec<String> {
+    let mut lines: Vec<String> = stdout
+        .lines()
+        .filter_map(|line| line.rsplit('\r').next())
+        .map(|line| line.trim_end().to_string())
+        .collect();
+
+    if !success {
+        lines.extend(
+            stderr
+                .lines()
+                .filter(|line| !line.trim().is_empty())
+                .map(str::to_string),
+        );
+    }
+
+    lines
+}
+
+/// Number of characters displayed, ignoring ANSI escape sequences.
+fn display_width(line: &str) -> usize {
+    let mut width = 0;
+    let mut in_escape = false;
+    for c in line.chars() {
+        match (in_escape, c) {
+            (false, '\x1b') => in_escape = true,
+            (true, 'm') => in_escape = false,
+            (false, _) => width += 1,
+            _ => {}
+        }
+    }
+    width
+}
+
+fn print_side_by_side(left: &[String], right: &[String]) {
+    println!("{ANSI_BOLD}{:<COLUMN_WIDTH$}Input{ANSI_RESET}", "Examples");
+    println!("{:<COLUMN_WIDTH$}-----", "--------");
+
+    for idx in 0..left.len().max(right.len()) {
+        let left = left.get(idx).map_or("", String::as_str);
+        let right = right.get(idx).map_or("", String::as_str);
+        let padding = COLUMN_WIDTH.saturating_sub(display_width(left)).max(1);
+        println!("{left}{}{right}", " ".repeat(padding));
+    }
+}
+
+/// The `--features` arguments of the cargo commands: the requested ones, and the encrypted
+/// inputs when the runner reads them too.
+fn feature_args(features: Option<&str>) -> Vec<&str> {
+    let mut args = vec![];
+    if let Some(features) = features {
+        args.extend(["--features", features]);
+    }
+    if cfg!(feature = "encrypted-inputs") {
+        args.extend(["--features", "encrypted-inputs"]);
+    }
+    args
+}
+
+fn run(day: Day, features: Option<&str>) {
+    let day_padded = day.to_string();
+    let features = feature_args(features);
+
+    print!("{ANSI_CLEAR_SCREEN}");
+    println!("{ANSI_BOLD}Day {day}{ANSI_RESET} {ANSI_ITALIC}building...{ANSI_RESET}");
+
+    let text = |bytes: &[u8]| String::from_utf8_lossy(bytes).to_string();
+    let tests = run_cargo(&[&["test", "--bin", &day_padded], features.as_slice()].concat())
+        .map(|output| summarize_tests(&text(&output.stdout), &text(&output.stderr)))
+        .unwrap_or_default();
+    let answers = run_cargo(
+        &[
+            &["run", "--quiet", "--release", "--bin", &day_padded],
+            features.as_slice(),
+        ]
+        .concat(),
+    )
+    .map(|output| {
+        summarize_run(
+            &text(&output.stdout),
+            &text(&output.stderr),
+            output.status.success(),
+        )
+    })
+    .unwrap_or_default();
+
+    print!("{ANSI_CLEAR_SCREEN}");
+    println!("{ANSI_BOLD}Day {day}{ANSI_RESET}\n");
+    print_side_by_side(&tests, &answers);
+    println!("\n{ANSI_ITALIC}Watching for changes, press Ctrl+C to stop.{ANSI_RESET}");
+}
+
+pub fn handle(day: Day, features: Option<&str>) {
+    let mut last_snapshot: Option<Snapshot> = None;
+
+    loop {
+        let current = snapshot(day);
+        let changed = last_snapshot
+            .as_ref()
+            .is_none_or(|last| !changed_files(last, &current).is_empty());
+        if changed {
+            run(day, features);
+            // the run may take a while, changes made meanwhile trigger another one.
+            last_snapshot = Some(current);
+        }
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day;
+
+    fn lines(text: &str) -> Vec<String> {
+        text.lines().map(str::to_string).collect()
+    }
+
+    #[test]
+    fn watches_the_examples_of_the_day() {
+        assert!(is_example_of(day!(7), "07.txt"));
+        assert!(is_example_of(day!(7), "07-2.txt"));
+        assert!(!is_example_of(day!(7), "17.txt"));
+        assert!(!is_example_of(day!(7), "07.txt.swp"));
+    }
+
+    #[test]
+    fn watches_every_source() {
+        let files = watched_files(day!(1));
+        assert!(files.contains(&paths::bin_file(day!(1))));
+        assert!(files.contains(&paths::resolve("src/lib.rs")));
+        assert!(files.contains(&paths::resolve("src/template/commands/watch.rs")));
+
+        let features = feature_args(Some("dhat-heap"));
+        assert_eq!(features[..2], ["--features", "dhat-heap"]);
+    }
+
+    #[test]
+    fn detects_changed_files() {
+        let time = |secs| Some(SystemTime::UNIX_EPOCH + Duration::from_secs(secs));
+        let last: Snapshot = [
+            (PathBuf::from("a"), time(1)),
+            (PathBuf::from("b"), time(1)),
+            (PathBuf::from("c"), None),
+        ]
+        .into();
+
+        assert!(changed_files(&last, &last).is_empty());
+
+        let current: Snapshot = [
+            (PathBuf::from("a"), time(1)),
+            (PathBuf::from("b"), time(2)),
+            (PathBuf::from("c"), time(2)),
+            (PathBuf::from("d"), time(1)),
+        ]
+        .into();
+        assert_eq!(
+            changed_files(&last, &current),
+            vec![PathBuf::from("b"), PathBuf::from("c"), PathBuf::from("d")]
+        );
+        assert_eq!(
+            changed_files(&current, &last),
+            vec![PathBuf::from("b"), PathBuf::from("c"), PathBuf::from("d")]
+        );
+    }
+
+    #[test]
+    fn summarizes_outputs() {
+        let stdout = "running 2 tests\ntest tests::test_part_one ... ok\ntest tests::test_part_two ... FAILED\n  left: Some(1)\n right: Some(2)\n\ntest result: FAILED.";
+        assert_eq!(
+            summarize_tests(stdout, ""),
+            lines("✔ test_part_one\n✖ test_part_two\n    left: Some(1)\n    right: Some(2)")
+        );
+
+        let stderr =
+            "   Compiling advent_of_code\nerror[E0308]: mismatched types\n --> src/bin/07.rs:3:5";
+        assert_eq!(
+            summarize_tests("", stderr),
+            lines("error[E0308]: mismatched types\n --> src/bin/07.rs:3:5")
+        );
+
+        let stdout = "Part 1: 12 (1ms)\rPart 1: 42 (2ms)\nPart 2: ✖";
+        assert_eq!(
+            summarize_run(stdout, "panicked", true),
+            lines("Part 1: 42 (2ms)\nPart 2: ✖")
+        );
+        assert_eq!(summarize_run("", "\npanicked\n", false), lines("panicked"));
+        assert_eq!(display_width("\x1b[1m42\x1b[0m ms"), 5);
+    }
+}
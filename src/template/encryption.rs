@@ -0,0 +1,165 @@
+/// Encrypted storage of the puzzle inputs, behind the `encrypted-inputs` feature.
+///
+/// Inputs are stored next to their plain path with an `.enc` extension, as the hex encoding of
+/// an AES-256-GCM nonce followed by the ciphertext. The key is derived from the passphrase in
+/// [`KEY_ENV`]. The nonce is derived from the key and the content, so encrypting the same input
+/// twice gives the same file and does not show up as a change in git.
+use std::{
+    env,
+    ffi::OsString,
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use aes_gcm::{
+    aead::{Aead, KeyInit},
+    Aes256Gcm, Key, Nonce,
+};
+use sha2::{Digest, Sha256};
+
+/// Environment variable holding the passphrase of the inputs.
+pub const KEY_ENV: &str = "AOC_INPUT_KEY";
+
+const NONCE_LEN: usize = 12;
+
+#[derive(Debug)]
+pub enum EncryptionError {
+    MissingKey,
+    /// The file is not valid hex or too short to hold a nonce.
+    Malformed,
+    /// Decryption failed, either the key is wrong or the file was tampered with.
+    Decryption,
+    IO(io::Error),
+}
+
+impl Display for EncryptionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EncryptionError::MissingKey => {
+                write!(f, "set `{KEY_ENV}` to read and write encrypted inputs.")
+            }
+            EncryptionError::Malformed => write!(f, "the encrypted input is malformed."),
+            EncryptionError::Decryption => {
+                write!(f, "could not decrypt the input, is `{KEY_ENV}` right?")
+            }
+            EncryptionError::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl std::error::Error for EncryptionError {}
+
+impl From<io::Error> for EncryptionError {
+    fn from(e: io::Error) -> Self {
+        EncryptionError::IO(e)
+    }
+}
+
+impl From<EncryptionError> for io::Error {
+    fn from(e: EncryptionError) -> Self {
+        match e {
+            EncryptionError::IO(e) => e,
+            e => io::Error::new(io::ErrorKind::InvalidData, e),
+        }
+    }
+}
+
+/// Returns the path of the encrypted form of a file, e.g. `data/inputs/01.txt.enc`.
+pub fn encrypted_path(path: &Path) -> PathBuf {
+    let mut file_name = path.file_name().map(OsString::from).unwrap_or_default();
+    file_name.push(".enc");
+    path.with_file_name(file_name)
+}
+
+fn key_from_env() -> Result<Key<Aes256Gcm>, EncryptionError> {
+    match env::var(KEY_ENV) {
+        Ok(passphrase) if !passphrase.is_empty() => Ok(Sha256::digest(passphrase.as_bytes())),
+        _ => Err(EncryptionError::MissingKey),
+    }
+}
+
+pub fn encrypt(plain: &str, key: &Key<Aes256Gcm>) -> String {
+    let digest = Sha256::new()
+        .chain_update(key)
+        .chain_update(plain.as_bytes())
+        .finalize();
+    let nonce = Nonce::from_slice(&digest[..NONCE_LEN]);
+
+    let ciphertext = Aes256Gcm::new(key)
+        .encrypt(nonce, plain.as_bytes())
+        .expect("AES-GCM encryption of an in-memory buffer cannot fail");
+
+    let mut encoded = to_hex(nonce);
+    encoded.push_str(&to_hex(&ciphertext));
+    encoded.push('\n');
+    encoded
+}
+
+pub fn decrypt(encoded: &str, key: &Key<Aes256Gcm>) -> Result<String, EncryptionError> {
+    let bytes = from_hex(encoded.trim()).ok_or(EncryptionError::Malformed)?;
+    if bytes.len() < NONCE_LEN {
+        return Err(EncryptionError::Malformed);
+    }
+
+    let (nonce, ciphertext) = bytes.split_at(NONCE_LEN);
+    let plain = Aes256Gcm::new(key)
+        .decrypt(Nonce::from_slice(nonce), ciphertext)
+        .map_err(|_| EncryptionError::Decryption)?;
+
+    String::from_utf8(plain).map_err(|_| EncryptionError::Decryption)
+}
+
+/// Reads and decrypts the encrypted form of `path`.
+pub fn read(path: &Path) -> Result<String, EncryptionError> {
+    let encoded = fs::read_to_string(encrypted_path(path))?;
+    decrypt(&encoded, &key_from_env()?)
+}
+
+/// Writes the encrypted form of `path`.
+pub fn write(path: &Path, plain: &str) -> Result<PathBuf, EncryptionError> {
+    let target = encrypted_path(path);
+    fs::write(&target, encrypt(plain, &key_from_env()?))?;
+    Ok(target)
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+fn from_hex(s: &str) -> Option<Vec<u8>> {
+    if !s.len().is_multiple_of(2) {
+        return None;
+    }
+
+    (0..s.len())
+        .step_by(2)
+        .map(|idx| u8::from_str_radix(s.get(idx..idx + 2)?, 16).ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_inputs() {
+        let key = Sha256::digest(b"hunter2");
+        let encoded = encrypt("3   4\n4   3\n", &key);
+
+        assert_eq!(encoded, encrypt("3   4\n4   3\n", &key));
+        assert_eq!(decrypt(&encoded, &key).unwrap(), "3   4\n4   3\n");
+        assert!(matches!(
+            decrypt(&encoded, &Sha256::digest(b"hunter3")),
+            Err(EncryptionError::Decryption)
+        ));
+        assert!(matches!(
+            decrypt("abc", &key),
+            Err(EncryptionError::Malformed)
+        ));
+        assert_eq!(
+            encrypted_path(Path::new("data/inputs/01.txt")),
+            PathBuf::from("data/inputs/01.txt.enc")
+        );
+    }
+}
@@ -0,0 +1,148 @@
+/// Selection of the input a solution binary runs against.
+use std::{
+    fmt::Display,
+    io::{self, Read},
+    path::PathBuf,
+    process,
+};
+
+use crate::template::{paths, read_data_file, Day};
+
+/// Where a solution reads its input from.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum InputSource {
+    /// The puzzle input, `data/inputs/NN.txt`.
+    #[default]
+    Puzzle,
+    /// Any file, relative paths are resolved from the workspace root.
+    File(PathBuf),
+    Stdin,
+    /// `data/examples/NN.txt`, or a named example, e.g. `2` for `data/examples/NN-2.txt`.
+    Example(Option<String>),
+}
+
+impl InputSource {
+    /// Parses `--input <path>`, `--input -` and `--example [name]`.
+    pub fn from_args(args: &[String]) -> Result<InputSource, String> {
+        let position = |flag: &str| args.iter().position(|x| x == flag);
+
+        match (position("--input"), position("--example")) {
+            (Some(_), Some(_)) => Err("`--input` and `--example` cannot be used together".into()),
+            (Some(idx), None) => match args.get(idx + 1).map(String::as_str) {
+                Some("-") => Ok(InputSource::Stdin),
+                Some(path) if !path.starts_with("--") => Ok(InputSource::File(path.into())),
+                _ => Err("`--input` expects a path, or `-` to read from stdin".into()),
+            },
+            (None, Some(idx)) => Ok(InputSource::Example(
+                args.get(idx + 1)
+                    .filter(|name| !name.starts_with("--"))
+                    .cloned(),
+            )),
+            (None, None) => Ok(InputSource::Puzzle),
+        }
+    }
+
+    /// Converts the source back to arguments, so it can be forwarded to a child command.
+    pub fn to_args(&self) -> Vec<String> {
+        match self {
+            InputSource::Puzzle => vec![],
+            InputSource::File(path) => vec!["--input".into(), path.display().to_string()],
+            InputSource::Stdin => vec!["--input".into(), "-".into()],
+            InputSource::Example(None) => vec!["--example".into()],
+            InputSource::Example(Some(name)) => vec!["--example".into(), name.clone()],
+        }
+    }
+
+    /// Returns the file the input is read from, [`None`] for stdin.
+    pub fn path(&self, day: Day) -> Option<PathBuf> {
+        match self {
+            InputSource::Puzzle => Some(paths::input_file(day)),
+            InputSource::File(path) => Some(paths::resolve(path)),
+            InputSource::Stdin => None,
+            InputSource::Example(None) => Some(paths::example_file(day)),
+            InputSource::Example(Some(name)) => {
+                let part_file = paths::data_file("examples", &format!("{day}-{name}.txt"));
+                // fall back on the name as is, e.g. `--example custom.txt`.
+                Some(if part_file.exists() {
+                    part_file
+                } else {
+                    paths::data_file("examples", name)
+                })
+            }
+        }
+    }
+
+    pub fn read(&self, day: Day) -> Result<String, io::Error> {
+        match self.path(day) {
+            Some(path) => read_data_file(&path),
+            None => {
+                let mut input = String::new();
+                io::stdin().read_to_string(&mut input)?;
+                Ok(input)
+            }
+        }
+    }
+}
+
+impl Display for InputSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InputSource::Puzzle => write!(f, "puzzle input"),
+            InputSource::File(path) => write!(f, "{}", path.display()),
+            InputSource::Stdin => write!(f, "stdin"),
+            InputSource::Example(None) => write!(f, "example"),
+            InputSource::Example(Some(name)) => write!(f, "example `{name}`"),
+        }
+    }
+}
+
+/// Reads the input selected by the arguments of the current process, exits on failure.
+pub fn read_from_args(day: Day) -> String {
+    let args: Vec<String> = std::env::args().collect();
+
+    let source = InputSource::from_args(&args).unwrap_or_else(|e| {
+        eprintln!("{e}");
+        process::exit(1);
+    });
+
+    source.read(day).unwrap_or_else(|e| {
+        match source.path(day) {
+            Some(path) => eprintln!("Could not read {source} ({}): {e}", path.display()),
+            None => eprintln!("Could not read {source}: {e}"),
+        }
+        process::exit(1);
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn parses_input_sources() {
+        assert_eq!(
+            InputSource::from_args(&args(&["01", "--time"])),
+            Ok(InputSource::Puzzle)
+        );
+        assert_eq!(
+            InputSource::from_args(&args(&["01", "--input", "-"])),
+            Ok(InputSource::Stdin)
+        );
+        assert_eq!(
+            InputSource::from_args(&args(&["01", "--example", "--time"])),
+            Ok(InputSource::Example(None))
+        );
+        assert_eq!(
+            InputSource::from_args(&args(&["01", "--example", "2"])),
+            Ok(InputSource::Example(Some("2".into())))
+        );
+        assert!(InputSource::from_args(&args(&["01", "--input"])).is_err());
+
+        let source = InputSource::File("inputs/alice.txt".into());
+        assert_eq!(InputSource::from_args(&source.to_args()), Ok(source));
+    }
+}
@@ -0,0 +1,157 @@
+/// Sanity checks of the downloaded inputs.
+///
+/// A failed download can leave an empty file or the HTML of an error page instead of the input.
+/// The checksum of each valid download is kept in a manifest, so a local edit of an input is
+/// noticed too.
+use std::{collections::BTreeMap, fmt::Display, fs, io};
+
+use crate::template::{paths, read_data_file, Day};
+
+/// Printed by adventofcode.com instead of the input when the session cookie is missing or expired.
+const LOGIN_MESSAGE: &str = "Please log in to get your puzzle input";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputProblem {
+    Missing,
+    Empty,
+    /// The file holds a web page, usually the login or an error page.
+    Html,
+    /// The input changed since it was downloaded.
+    ChecksumMismatch,
+}
+
+impl Display for InputProblem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InputProblem::Missing => write!(f, "the input file does not exist"),
+            InputProblem::Empty => write!(f, "the input file is empty"),
+            InputProblem::Html => write!(
+                f,
+                "the input file looks like a login or error page, check your session cookie"
+            ),
+            InputProblem::ChecksumMismatch => {
+                write!(f, "the input file changed since it was downloaded")
+            }
+        }
+    }
+}
+
+/// Detects content that cannot be a puzzle input.
+pub fn check_content(content: &str) -> Result<(), InputProblem> {
+    let trimmed = content.trim_start();
+    if trimmed.is_empty() {
+        return Err(InputProblem::Empty);
+    }
+
+    let head = trimmed
+        .get(..trimmed.len().min(64))
+        .unwrap_or(trimmed)
+        .to_lowercase();
+    if head.starts_with("<!doctype") || head.starts_with("<html") || content.contains(LOGIN_MESSAGE)
+    {
+        return Err(InputProblem::Html);
+    }
+
+    Ok(())
+}
+
+/// Checks the input of a day, including its checksum when the manifest has one.
+pub fn check_input(day: Day) -> Result<(), InputProblem> {
+    let content = read_data_file(&paths::input_file(day)).map_err(|_| InputProblem::Missing)?;
+
+    check_content(&content)?;
+
+    match Manifest::read().get(day) {
+        Some(expected) if expected != checksum(&content) => Err(InputProblem::ChecksumMismatch),
+        _ => Ok(()),
+    }
+}
+
+/// 64-bit FNV-1a hash, stable across platforms and compiler versions.
+pub fn checksum(content: &str) -> u64 {
+    content.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0000_0100_0000_01b3)
+    })
+}
+
+/// Checksums of the downloaded inputs, stored as `NN <hex checksum>` lines.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Manifest {
+    checksums: BTreeMap<Day, u64>,
+}
+
+impl Manifest {
+    /// Reads the manifest file. A missing or malformed file gives an empty manifest.
+    pub fn read() -> Manifest {
+        fs::read_to_string(paths::checksums_file())
+            .map(|content| Manifest::parse(&content))
+            .unwrap_or_default()
+    }
+
+    pub fn parse(content: &str) -> Manifest {
+        let checksums = content
+            .lines()
+            .filter_map(|line| {
+                let (day, hash) = line.split_once(' ')?;
+                Some((
+                    day.parse().ok()?,
+                    u64::from_str_radix(hash.trim(), 16).ok()?,
+                ))
+            })
+            .collect();
+
+        Manifest { checksums }
+    }
+
+    pub fn get(&self, day: Day) -> Option<u64> {
+        self.checksums.get(&day).copied()
+    }
+
+    pub fn insert(&mut self, day: Day, content: &str) {
+        self.checksums.insert(day, checksum(content));
+    }
+
+    pub fn store(&self) -> Result<(), io::Error> {
+        fs::write(paths::checksums_file(), self.to_string())
+    }
+}
+
+impl Display for Manifest {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.checksums
+            .iter()
+            .try_for_each(|(day, hash)| writeln!(f, "{day} {hash:016x}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day;
+
+    #[test]
+    fn detects_invalid_content() {
+        assert_eq!(check_content("1 2\n3 4\n"), Ok(()));
+        assert_eq!(check_content(" \n"), Err(InputProblem::Empty));
+        assert_eq!(
+            check_content("<!DOCTYPE html>\n<html lang=\"en-us\">"),
+            Err(InputProblem::Html)
+        );
+        assert_eq!(
+            check_content("Puzzle inputs differ by user.  Please log in to get your puzzle input."),
+            Err(InputProblem::Html)
+        );
+    }
+
+    #[test]
+    fn round_trips_manifest() {
+        let mut manifest = Manifest::default();
+        manifest.insert(day!(3), "mul(2,4)");
+        manifest.insert(day!(1), "3   4");
+
+        let content = manifest.to_string();
+        assert!(content.starts_with("01 "));
+        assert_eq!(Manifest::parse(&content), manifest);
+        assert_eq!(manifest.get(day!(3)), Some(checksum("mul(2,4)")));
+    }
+}
@@ -1,7 +1,13 @@
-use std::{env, fs};
+use std::{fs, io, path::Path};
 
 pub mod aoc_cli;
 pub mod commands;
+#[cfg(feature = "encrypted-inputs")]
+pub mod encryption;
+pub mod input;
+pub mod integrity;
+pub mod paths;
+pub mod puzzle;
 pub mod runner;
 
 pub use day::*;
@@ -14,26 +20,36 @@ mod timings;
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
+pub const ANSI_CLEAR_SCREEN: &str = "\x1b[2J\x1b[H";
+
+/// Reads a file of the data directory. With the `encrypted-inputs` feature, a missing file is
+/// decrypted from its `.enc` counterpart.
+pub fn read_data_file(path: &Path) -> Result<String, io::Error> {
+    match fs::read_to_string(path) {
+        #[cfg(feature = "encrypted-inputs")]
+        Err(e)
+            if e.kind() == io::ErrorKind::NotFound && encryption::encrypted_path(path).exists() =>
+        {
+            Ok(encryption::read(path)?)
+        }
+        result => result,
+    }
+}
 
 /// Helper function that reads a text file to a string.
 #[must_use]
 pub fn read_file(folder: &str, day: Day) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+    let filepath = paths::data_file(folder, &format!("{day}.txt"));
+    let f = read_data_file(&filepath);
+    f.unwrap_or_else(|e| panic!("could not open input file {}: {e}", filepath.display()))
 }
 
 /// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
 #[must_use]
 pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd
-        .join("data")
-        .join(folder)
-        .join(format!("{day}-{part}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+    let filepath = paths::data_file(folder, &format!("{day}-{part}.txt"));
+    let f = read_data_file(&filepath);
+    f.unwrap_or_else(|e| panic!("could not open input file {}: {e}", filepath.display()))
 }
 
 /// Creates the constant `DAY` and sets up the input and runner for each part.
@@ -61,8 +77,14 @@ macro_rules! solution {
 
         fn main() {
             use $crate::template::runner::*;
-            let input = $crate::template::read_file("inputs", DAY);
-            $( run_part($func, &input, DAY, $part); )*
+            let input = $crate::template::input::read_from_args(DAY);
+            let mut success = true;
+            $( success &= run_part($func, &input, DAY, $part); )*
+
+            // signals the failure of a part to `run_multi`.
+            if !success {
+                std::process::exit(1);
+            }
         }
     };
 }
@@ -0,0 +1,126 @@
+/// Resolution of the workspace files, independent of the current directory.
+///
+/// The workspace root is found with `CARGO_MANIFEST_DIR` (at runtime when started through cargo,
+/// otherwise the one the crate was built with) and falls back on the first ancestor of the
+/// current directory containing [`ROOT_MARKER`]. The data directory defaults to `<root>/data`
+/// and can be overridden with the [`DATA_DIR_ENV`] environment variable.
+use std::{
+    env,
+    path::{Path, PathBuf},
+    sync::OnceLock,
+};
+
+use crate::template::Day;
+
+/// Environment variable overriding the data directory. Relative paths start at the workspace root.
+pub const DATA_DIR_ENV: &str = "AOC_DATA_DIR";
+
+/// Environment variable overriding the user template directory, see [`templates_dir`].
+pub const TEMPLATE_DIR_ENV: &str = "AOC_TEMPLATE_DIR";
+
+/// File identifying the workspace root when `CARGO_MANIFEST_DIR` is not usable.
+pub const ROOT_MARKER: &str = ".cargo/config.toml";
+
+/// Returns the workspace root, the directory containing `Cargo.toml`.
+pub fn root() -> &'static Path {
+    static ROOT: OnceLock<PathBuf> = OnceLock::new();
+    ROOT.get_or_init(find_root)
+}
+
+fn find_root() -> PathBuf {
+    let manifest_dirs = [
+        env::var_os("CARGO_MANIFEST_DIR").map(PathBuf::from),
+        Some(PathBuf::from(env!("CARGO_MANIFEST_DIR"))),
+    ];
+
+    if let Some(dir) = manifest_dirs
+        .into_iter()
+        .flatten()
+        .find(|dir| dir.join(ROOT_MARKER).exists())
+    {
+        return dir;
+    }
+
+    let cwd = env::current_dir().unwrap_or_default();
+    cwd.ancestors()
+        .find(|dir| dir.join(ROOT_MARKER).exists())
+        .map_or(cwd.clone(), Path::to_path_buf)
+}
+
+/// Resolves a path relative to the workspace root. Absolute paths are returned as is.
+pub fn resolve(path: impl AsRef<Path>) -> PathBuf {
+    root().join(path)
+}
+
+/// Returns the data directory, `data/` unless overridden with [`DATA_DIR_ENV`].
+pub fn data_dir() -> PathBuf {
+    match env::var_os(DATA_DIR_ENV) {
+        Some(dir) if !dir.is_empty() => resolve(dir),
+        _ => resolve("data"),
+    }
+}
+
+/// Returns the path of a file in a folder of the data directory, e.g. `data/examples/01-2.txt`.
+pub fn data_file(folder: &str, file_name: &str) -> PathBuf {
+    data_dir().join(folder).join(file_name)
+}
+
+pub fn input_file(day: Day) -> PathBuf {
+    data_file("inputs", &format!("{day}.txt"))
+}
+
+pub fn example_file(day: Day) -> PathBuf {
+    data_file("examples", &format!("{day}.txt"))
+}
+
+pub fn puzzle_file(day: Day) -> PathBuf {
+    data_file("puzzles", &format!("{day}.md"))
+}
+
+/// Checksums of the downloaded inputs, see [`crate::template::integrity`].
+pub fn checksums_file() -> PathBuf {
+    data_dir().join("checksums.txt")
+}
+
+pub fn timings_file() -> PathBuf {
+    data_dir().join("timings.json")
+}
+
+/// Returns the visualization output directory of a day, `data/viz/NN`.
+pub fn viz_dir(day: Day) -> PathBuf {
+    data_dir().join("viz").join(day.to_string())
+}
+
+pub fn bin_file(day: Day) -> PathBuf {
+    resolve(format!("src/bin/{day}.rs"))
+}
+
+/// Returns the directory of the user templates, `templates/` unless overridden with
+/// [`TEMPLATE_DIR_ENV`].
+pub fn templates_dir() -> PathBuf {
+    match env::var_os(TEMPLATE_DIR_ENV) {
+        Some(dir) if !dir.is_empty() => resolve(dir),
+        _ => resolve("templates"),
+    }
+}
+
+/// Module listing the days for in-process running, updated by `scaffold` when it exists.
+pub fn registry_file() -> PathBuf {
+    resolve("src/registry.rs")
+}
+
+pub fn readme_file() -> PathBuf {
+    resolve("README.md")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolves_from_workspace_root() {
+        assert!(root().join("Cargo.toml").exists());
+        assert_eq!(resolve("/tmp/input.txt"), PathBuf::from("/tmp/input.txt"));
+        assert!(bin_file(crate::day!(1)).ends_with("src/bin/01.rs"));
+    }
+}
@@ -0,0 +1,273 @@
+/// Information extracted from the puzzle descriptions downloaded to `data/puzzles/NN.md`.
+use crate::template::{paths, Day};
+
+/// Returns the puzzle description of a day, if it has been downloaded.
+pub fn read(day: Day) -> Option<String> {
+    std::fs::read_to_string(paths::puzzle_file(day)).ok()
+}
+
+/// Extracts the title from the `--- Day 1: Historian Hysteria ---` heading.
+pub fn title(markdown: &str) -> Option<String> {
+    markdown.lines().find_map(|line| {
+        let heading = line.split_once("--- Day ")?.1;
+        let title = heading.split_once(": ")?.1;
+        let title = title.split(" ---").next()?.trim();
+        (!title.is_empty()).then(|| title.to_string())
+    })
+}
+
+/// Returns the URL of the puzzle of a day.
+pub fn url(year: Option<u16>, day: Day) -> String {
+    match year {
+        Some(year) => format!("https://adventofcode.com/{year}/day/{}", day.into_inner()),
+        None => "https://adventofcode.com".into(),
+    }
+}
+
+/// Heading starting the second part of a puzzle.
+const PART_TWO_HEADING: &str = "--- Part Two ---";
+
+/// Returns the description of part two, from its heading to the end.
+pub fn part_two(markdown: &str) -> Option<&str> {
+    let heading = markdown.find(PART_TWO_HEADING)?;
+    let line_start = markdown[..heading].rfind('\n').map_or(0, |idx| idx + 1);
+    Some(markdown[line_start..].trim_end())
+}
+
+/// Example input and expected answer of a part, as guessed from the description.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Example {
+    /// [`None`] when the part reuses the example of part one.
+    pub input: Option<String>,
+    pub answer: Option<String>,
+    /// Whether the guess is unambiguous, otherwise it should be confirmed.
+    pub confident: bool,
+}
+
+#[derive(Debug)]
+struct CodeBlock {
+    /// Indices of the opening and closing fences.
+    start: usize,
+    end: usize,
+    text: String,
+}
+
+/// Returns the code blocks of the lines, fenced with three backticks.
+fn code_blocks(lines: &[&str]) -> Vec<CodeBlock> {
+    let mut blocks = vec![];
+    let mut current: Option<CodeBlock> = None;
+
+    for (idx, line) in lines.iter().enumerate() {
+        if !line.trim_start().starts_with("```") {
+            if let Some(block) = current.as_mut() {
+                block.text.push_str(line);
+                block.text.push('\n');
+            }
+            continue;
+        }
+
+        match current.take() {
+            Some(block) => blocks.push(CodeBlock { end: idx, ..block }),
+            None => {
+                current = Some(CodeBlock {
+                    start: idx,
+                    end: idx,
+                    text: String::new(),
+                })
+            }
+        }
+    }
+
+    blocks
+}
+
+/// Returns the values emphasized as code in a line, e.g. `` `*11*` `` or ``*`11`*``.
+fn emphasized_values(line: &str) -> Vec<String> {
+    let mut values = vec![];
+    for (open, close) in [("`*", "*`"), ("*`", "`*")] {
+        let mut rest = line;
+        while let Some(start) = rest.find(open) {
+            let after = &rest[start + open.len()..];
+            let Some(end) = after.find(close) else {
+                break;
+            };
+            let value = after[..end].trim();
+            if !value.is_empty() {
+                values.push(value.to_string());
+            }
+            rest = &after[end + close.len()..];
+        }
+    }
+    values
+}
+
+/// Returns the paragraph (consecutive non-blank lines) around a line.
+fn paragraph<'a>(lines: &[&'a str], idx: usize) -> Vec<&'a str> {
+    let is_blank = |line: &&str| line.trim().is_empty();
+    let start = lines[..idx]
+        .iter()
+        .rposition(is_blank)
+        .map_or(0, |blank| blank + 1);
+    let end = lines[idx..]
+        .iter()
+        .position(is_blank)
+        .map_or(lines.len(), |blank| idx + blank);
+    lines[start..end].to_vec()
+}
+
+fn mentions_example(lines: &[&str]) -> bool {
+    lines
+        .iter()
+        .any(|line| line.to_lowercase().contains("example"))
+}
+
+/// Guesses the example of the lines of a part.
+fn part_example(lines: &[&str], is_part_two: bool) -> Option<Example> {
+    let blocks = code_blocks(lines);
+    let in_code = |idx: usize| {
+        blocks
+            .iter()
+            .any(|block| (block.start..=block.end).contains(&idx))
+    };
+
+    // an example is introduced by a sentence such as "For example:".
+    let candidates: Vec<&CodeBlock> = blocks
+        .iter()
+        .filter(|block| !block.text.trim().is_empty())
+        .filter(|block| {
+            let before: Vec<&str> = lines[..block.start]
+                .iter()
+                .rev()
+                .filter(|line| !line.trim().is_empty())
+                .take(2)
+                .copied()
+                .collect();
+            mentions_example(&before)
+        })
+        .collect();
+
+    let example_block = candidates.first().copied().or_else(|| {
+        // part two usually reuses the example of part one.
+        (!is_part_two).then(|| blocks.iter().max_by_key(|block| block.text.len()))?
+    });
+
+    let search_from = example_block.map_or(0, |block| block.start);
+    let answer = (search_from..lines.len())
+        .filter(|idx| !in_code(*idx))
+        .flat_map(|idx| {
+            emphasized_values(lines[idx])
+                .into_iter()
+                .map(move |value| (idx, value))
+        })
+        .last();
+
+    if example_block.is_none() && answer.is_none() {
+        return None;
+    }
+
+    let confident = candidates.len() <= 1
+        && (example_block.is_some() || is_part_two)
+        && answer
+            .as_ref()
+            .is_some_and(|(idx, _)| mentions_example(&paragraph(lines, *idx)));
+
+    Some(Example {
+        input: example_block.map(|block| block.text.clone()),
+        answer: answer.map(|(_, value)| value),
+        confident,
+    })
+}
+
+/// Guesses the example input and expected answer of both parts from a puzzle description.
+pub fn examples(markdown: &str) -> [Option<Example>; 2] {
+    let lines: Vec<&str> = markdown.lines().collect();
+    let part_two = lines
+        .iter()
+        .position(|line| line.contains(PART_TWO_HEADING))
+        .unwrap_or(lines.len());
+
+    [
+        part_example(&lines[..part_two], false),
+        part_example(&lines[part_two..], true),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day;
+
+    #[test]
+    fn extracts_title() {
+        let markdown =
+            "\\--- Day 1: Historian Hysteria ---\n----------\n\nThe *Chief Historian*...";
+        assert_eq!(title(markdown), Some("Historian Hysteria".into()));
+        assert_eq!(
+            title("## --- Day 24: Crossed Wires ---"),
+            Some("Crossed Wires".into())
+        );
+        assert_eq!(title("no heading"), None);
+        assert_eq!(
+            url(Some(2024), day!(5)),
+            "https://adventofcode.com/2024/day/5"
+        );
+    }
+
+    const PUZZLE: &str = "\\--- Day 1: Historian Hysteria ---
+----------
+
+For example:
+
+```
+3   4
+4   3
+```
+
+In the example list above, the total distance is `*11*`!
+
+What is the total distance between your lists?
+
+\\--- Part Two ---
+----------
+
+So, for these example lists, the similarity score at the end of this process is `*31*`.
+";
+
+    #[test]
+    fn extracts_examples() {
+        let [part_one, part_two] = examples(PUZZLE);
+        assert_eq!(
+            part_one,
+            Some(Example {
+                input: Some("3   4\n4   3\n".into()),
+                answer: Some("11".into()),
+                confident: true,
+            })
+        );
+        assert_eq!(
+            part_two,
+            Some(Example {
+                input: None,
+                answer: Some("31".into()),
+                confident: true,
+            })
+        );
+    }
+
+    #[test]
+    fn extracts_part_two() {
+        assert!(part_two(PUZZLE)
+            .unwrap()
+            .starts_with("\\--- Part Two ---\n----------\n\nSo, for these"));
+        assert_eq!(part_two("\\--- Day 1: Historian Hysteria ---"), None);
+    }
+
+    #[test]
+    fn is_unsure_without_example_sentence() {
+        let [part_one, part_two] = examples("```\n1\n```\n\nThe answer is `*1*`.\n");
+        let part_one = part_one.unwrap();
+        assert_eq!(part_one.answer, Some("1".into()));
+        assert!(!part_one.confident);
+        assert_eq!(part_two, None);
+    }
+}
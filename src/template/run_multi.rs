@@ -1,14 +1,26 @@
-use std::{collections::HashSet, io};
+use std::{collections::HashSet, io, path::PathBuf};
 
-use crate::template::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{paths, Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
 
 use super::{
     all_days,
+    runner::{ERROR_MARKER, NOT_FOUND_MARKER, NOT_IMPLEMENTED_MARKER, PANIC_MARKER},
     timings::{Timing, Timings},
 };
+use child_commands::PartStatus;
+
+/// Result of running several solutions.
+pub struct MultiRun {
+    /// Only collected when the solutions are timed.
+    pub timings: Option<Timings>,
+    /// Days whose binary exited with a non-zero exit code.
+    pub failed_days: Vec<Day>,
+}
 
-pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
+pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> MultiRun {
     let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
+    let mut statuses: Vec<(Day, u8, PartStatus)> = vec![];
+    let mut failed_days: Vec<Day> = vec![];
 
     let mut need_space = false;
 
@@ -24,17 +36,30 @@ pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -
             println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
             println!("------");
 
-            let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+            let (output, success) =
+                child_commands::run_solution(day, is_timed, is_release).unwrap();
+
+            if !success {
+                failed_days.push(day);
+            }
 
-            if output.is_empty() {
+            if output.is_empty() && success {
                 println!("Not solved.");
             } else {
                 let val = child_commands::parse_exec_time(&output, day);
                 timings.push(val);
+
+                statuses.extend(
+                    child_commands::parse_part_statuses(&output)
+                        .into_iter()
+                        .map(|(part, status)| (day, part, status)),
+                );
             }
         });
 
-    if is_timed {
+    print_summary(&statuses, &failed_days);
+
+    let timings = if is_timed {
         let timings = Timings { data: timings };
         let total_millis = timings.total_millis();
         println!(
@@ -43,6 +68,58 @@ pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -
         Some(timings)
     } else {
         None
+    };
+
+    MultiRun {
+        timings,
+        failed_days,
+    }
+}
+
+fn print_summary(statuses: &[(Day, u8, PartStatus)], failed_days: &[Day]) {
+    let count = |f: fn(&PartStatus) -> bool| statuses.iter().filter(|(_, _, s)| f(s)).count();
+
+    println!("\n{ANSI_BOLD}Summary{ANSI_RESET}");
+    println!("------");
+    println!(
+        "✔ {} solved, {} {}, {} {}",
+        count(|s| *s == PartStatus::Solved),
+        count(|s| *s == PartStatus::NotImplemented),
+        NOT_IMPLEMENTED_MARKER,
+        count(|s| *s == PartStatus::NotFound),
+        NOT_FOUND_MARKER,
+    );
+
+    let errors = count(|s| matches!(s, PartStatus::Error(_)));
+    if errors > 0 {
+        println!("{ERROR_MARKER} {errors} failed");
+    }
+
+    let panics = count(|s| matches!(s, PartStatus::Panicked(_)));
+    if panics > 0 {
+        println!("{PANIC_MARKER} {panics} part(s)");
+    }
+
+    for (day, part, status) in statuses {
+        match status {
+            PartStatus::NotFound => println!("Day {day} Part {part}: {NOT_FOUND_MARKER}"),
+            PartStatus::Error(message) => {
+                println!("Day {day} Part {part}: {ERROR_MARKER} {message}");
+            }
+            PartStatus::Panicked(report) => {
+                println!("Day {day} Part {part}: {PANIC_MARKER} {report}");
+            }
+            _ => {}
+        }
+    }
+
+    // a binary can also fail without printing a part status, e.g. when it aborts.
+    for day in failed_days {
+        if !statuses.iter().any(|(d, _, s)| {
+            d == day && matches!(s, PartStatus::Error(_) | PartStatus::Panicked(_))
+        }) {
+            println!("Day {day}: exited with a failure before reporting its parts");
+        }
     }
 }
 
@@ -60,27 +137,34 @@ impl From<std::io::Error> for Error {
 }
 
 #[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+pub fn get_path_for_bin(day: Day) -> PathBuf {
+    paths::bin_file(day)
 }
 
 /// All solutions live in isolated binaries.
 /// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
 pub mod child_commands {
-    use super::{get_path_for_bin, Error};
+    use super::{
+        get_path_for_bin, Error, ERROR_MARKER, NOT_FOUND_MARKER, NOT_IMPLEMENTED_MARKER,
+        PANIC_MARKER,
+    };
     use crate::template::Day;
     use std::{
         io::{BufRead, BufReader},
-        path::Path,
         process::{Command, Stdio},
         thread,
     };
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
+    /// Run the solution bin for a given day.
+    /// Returns the lines printed to stdout and whether the binary exited successfully.
+    pub fn run_solution(
+        day: Day,
+        is_timed: bool,
+        is_release: bool,
+    ) -> Result<(Vec<String>, bool), Error> {
         // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
-            return Ok(vec![]);
+        if !get_path_for_bin(day).exists() {
+            return Ok((vec![], true));
         }
 
         let day_padded = day.to_string();
@@ -90,6 +174,10 @@ pub mod child_commands {
             args.push("--release");
         }
 
+        if cfg!(feature = "encrypted-inputs") {
+            args.extend(["--features", "encrypted-inputs"]);
+        }
+
         if is_timed {
             // mirror `--time` flag to child invocations.
             args.push("--");
@@ -123,9 +211,9 @@ pub mod child_commands {
         }
 
         thread.join().unwrap();
-        cmd.wait()?;
+        let status = cmd.wait()?;
 
-        Ok(output)
+        Ok((output, status.success()))
     }
 
     pub fn parse_exec_time(output: &[String], day: Day) -> super::Timing {
@@ -164,6 +252,43 @@ pub mod child_commands {
         timings
     }
 
+    /// Outcome of a part, as printed by the solution binary.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub enum PartStatus {
+        Solved,
+        NotImplemented,
+        NotFound,
+        Error(String),
+        Panicked(String),
+    }
+
+    pub fn parse_part_statuses(output: &[String]) -> Vec<(u8, PartStatus)> {
+        output
+            .iter()
+            .filter_map(|l| {
+                // intermediate results are overwritten with a carriage return.
+                let line = l.rsplit('\r').next()?;
+                let (part, result) = line.strip_prefix("Part ")?.split_once(": ")?;
+                let part = part.parse::<u8>().ok()?;
+                let result = result.trim_end();
+
+                let status = if result.starts_with(NOT_IMPLEMENTED_MARKER) {
+                    PartStatus::NotImplemented
+                } else if result.starts_with(NOT_FOUND_MARKER) {
+                    PartStatus::NotFound
+                } else if let Some(message) = result.strip_prefix(ERROR_MARKER) {
+                    PartStatus::Error(message.trim().to_string())
+                } else if let Some(report) = result.strip_prefix(PANIC_MARKER) {
+                    PartStatus::Panicked(report.trim().to_string())
+                } else {
+                    PartStatus::Solved
+                };
+
+                Some((part, status))
+            })
+            .collect()
+    }
+
     fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
         s.split(postfix).next()?.parse().ok()
     }
@@ -174,7 +299,7 @@ pub mod child_commands {
             .split(" samples)")
             .next()?
             .split('(')
-            .last()?
+            .next_back()?
             .split('@')
             .next()?
             .trim();
@@ -205,7 +330,7 @@ pub mod child_commands {
 
     #[cfg(feature = "test_lib")]
     mod tests {
-        use super::parse_exec_time;
+        use super::{parse_exec_time, parse_part_statuses, PartStatus};
 
         use crate::day;
 
@@ -253,5 +378,30 @@ pub mod child_commands {
             assert_eq!(res.part_1.is_none(), true);
             assert_eq!(res.part_2.is_none(), true);
         }
+
+        #[test]
+        fn parses_part_statuses() {
+            let res = parse_part_statuses(&[
+                "Part 1: ✖ not implemented\rPart 1: ✖ not implemented      ".into(),
+                "Part 2: ∅ no answer found".into(),
+                "Part 1: ⚠ error: line 2, column 3: unexpected tile `x`".into(),
+                "Part 2: 42 (1.2ms)".into(),
+                "Part 1: 💥 panicked: boom at src/bin/01.rs:3:5".into(),
+                "unrelated output".into(),
+            ]);
+            assert_eq!(
+                res,
+                vec![
+                    (1, PartStatus::NotImplemented),
+                    (2, PartStatus::NotFound),
+                    (
+                        1,
+                        PartStatus::Error("line 2, column 3: unexpected tile `x`".into())
+                    ),
+                    (2, PartStatus::Solved),
+                    (1, PartStatus::Panicked("boom at src/bin/01.rs:3:5".into())),
+                ]
+            );
+        }
     }
 }
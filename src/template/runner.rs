@@ -2,24 +2,161 @@
 use std::fmt::Display;
 use std::hint::black_box;
 use std::io::{stdout, Write};
+use std::panic::{self, AssertUnwindSafe};
 use std::process::Output;
+use std::sync::Mutex;
 use std::time::{Duration, Instant};
 use std::{cmp, env, process};
 
+use crate::template::commands::example;
+use crate::template::input::InputSource;
 use crate::template::ANSI_BOLD;
 use crate::template::{aoc_cli, Day, ANSI_ITALIC, ANSI_RESET};
 
-pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
+/// Printed in place of the answer of a part that returned [`None`].
+pub const NOT_IMPLEMENTED_MARKER: &str = "✖ not implemented";
+/// Printed in place of the answer of a part that returned [`Answer::NotFound`].
+pub const NOT_FOUND_MARKER: &str = "∅ no answer found";
+/// Printed before the message of a part that returned an error.
+pub const ERROR_MARKER: &str = "⚠ error:";
+/// Printed before the message and location of a part that panicked.
+pub const PANIC_MARKER: &str = "💥 panicked:";
+
+/// Outcome of a solution part.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer<T> {
+    Solved(T),
+    /// The part has not been written yet, e.g. the scaffolded `None`.
+    NotImplemented,
+    /// The solution ran fine but the input has no answer.
+    NotFound,
+    Error(String),
+    /// The part panicked, holds the panic message and its location.
+    Panicked(String),
+}
+
+impl<T> Answer<T> {
+    /// Whether the part returned an error or panicked.
+    pub fn is_failure(&self) -> bool {
+        matches!(self, Answer::Error(_) | Answer::Panicked(_))
+    }
+
+    /// Returns the answer if the part was solved.
+    pub fn solved(self) -> Option<T> {
+        match self {
+            Answer::Solved(value) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+/// Values that a solution part can return:
+///  1. `Option<T>`: [`None`] means that the part is not implemented.
+///  2. `Result<T, E>`: the error is displayed as the result of the part.
+///  3. `Result<Answer<T>, E>` or [`Answer<T>`] to report that no answer was found.
+pub trait IntoAnswer {
+    type Value: Display;
+
+    fn into_answer(self) -> Answer<Self::Value>;
+}
+
+impl<T: Display> IntoAnswer for Answer<T> {
+    type Value = T;
+
+    fn into_answer(self) -> Answer<T> {
+        self
+    }
+}
+
+impl<T: Display> IntoAnswer for Option<T> {
+    type Value = T;
+
+    fn into_answer(self) -> Answer<T> {
+        match self {
+            Some(value) => Answer::Solved(value),
+            None => Answer::NotImplemented,
+        }
+    }
+}
+
+impl<T: Display, E: Display> IntoAnswer for Result<T, E> {
+    type Value = T;
+
+    fn into_answer(self) -> Answer<T> {
+        match self {
+            Ok(value) => Answer::Solved(value),
+            Err(e) => Answer::Error(e.to_string()),
+        }
+    }
+}
+
+impl<T: Display, E: Display> IntoAnswer for Result<Answer<T>, E> {
+    type Value = T;
+
+    fn into_answer(self) -> Answer<T> {
+        match self {
+            Ok(answer) => answer,
+            Err(e) => Answer::Error(e.to_string()),
+        }
+    }
+}
+
+/// Runs, prints and optionally submits a solution part.
+/// Returns `false` if the part returned an error or panicked.
+pub fn run_part<I: Clone, R: IntoAnswer>(
+    func: impl Fn(I) -> R,
+    input: I,
+    day: Day,
+    part: u8,
+) -> bool {
     let part_str = format!("Part {part}");
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    let (result, duration, samples) = run_timed(
+        |input| catch_panic(|| func(input).into_answer()),
+        input,
+        |result| print_result(result, &part_str, ""),
+        |result| matches!(result, Answer::Solved(_)),
+    );
 
     print_result(&result, &part_str, &format_duration(&duration, samples));
 
-    if let Some(result) = result {
+    let is_failure = result.is_failure();
+    if let Answer::Solved(result) = result {
         submit_result(result, day, part);
     }
+
+    !is_failure
+}
+
+/// Message and location of the last panic, recorded by the hook installed in [`catch_panic`].
+static LAST_PANIC: Mutex<Option<String>> = Mutex::new(None);
+
+/// Runs `func`, turning an unwinding panic into [`Answer::Panicked`].
+/// The default hook is silenced meanwhile so the panic is only reported as the result of the part.
+fn catch_panic<T>(func: impl FnOnce() -> Answer<T>) -> Answer<T> {
+    let default_hook = panic::take_hook();
+    panic::set_hook(Box::new(|info| {
+        let message = info
+            .payload()
+            .downcast_ref::<&str>()
+            .map(ToString::to_string)
+            .or_else(|| info.payload().downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "Box<dyn Any>".into());
+
+        let report = match info.location() {
+            Some(location) => format!("{message} at {location}"),
+            None => message,
+        };
+        *LAST_PANIC.lock().unwrap_or_else(|e| e.into_inner()) = Some(report);
+    }));
+
+    let result = panic::catch_unwind(AssertUnwindSafe(func));
+    panic::set_hook(default_hook);
+
+    result.unwrap_or_else(|_| {
+        let report = LAST_PANIC.lock().unwrap_or_else(|e| e.into_inner()).take();
+        Answer::Panicked(report.unwrap_or_else(|| "unknown panic".into()))
+    })
 }
 
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
@@ -29,6 +166,7 @@ fn run_timed<I: Clone, T>(
     func: impl Fn(I) -> T,
     input: I,
     hook: impl Fn(&T),
+    should_bench: impl Fn(&T) -> bool,
 ) -> (T, Duration, u128) {
     let timer = Instant::now();
     let result = {
@@ -43,7 +181,7 @@ fn run_timed<I: Clone, T>(
 
     hook(&result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
+    let run = if std::env::args().any(|x| x == "--time") && should_bench(&result) {
         bench(func, input, &base_time)
     } else {
         (base_time, 1)
@@ -94,11 +232,11 @@ fn format_duration(duration: &Duration, samples: u128) -> String {
     }
 }
 
-fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
+fn print_result<T: Display>(result: &Answer<T>, part: &str, duration_str: &str) {
     let is_intermediate_result = duration_str.is_empty();
 
-    match result {
-        Some(result) => {
+    let status = match result {
+        Answer::Solved(result) => {
             if result.to_string().contains('\n') {
                 let str = format!("{part}: ▼ {duration_str}");
                 if is_intermediate_result {
@@ -117,21 +255,27 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
                     println!("{str}");
                 }
             }
+            return;
         }
-        None => {
-            if is_intermediate_result {
-                print!("{part}: ✖");
-            } else {
-                print!("\r");
-                println!("{part}: ✖             ");
-            }
-        }
+        Answer::NotImplemented => NOT_IMPLEMENTED_MARKER.to_string(),
+        Answer::NotFound => NOT_FOUND_MARKER.to_string(),
+        Answer::Error(message) => format!("{ERROR_MARKER} {message}"),
+        Answer::Panicked(report) => format!("{PANIC_MARKER} {report}"),
+    };
+
+    if is_intermediate_result {
+        print!("{part}: {status}");
+    } else {
+        print!("\r");
+        println!("{part}: {status}             ");
     }
 }
 
 /// Parse the arguments passed to `solve` and try to submit one part of the solution if:
 ///  1. we are in `--release` mode.
 ///  2. aoc-cli is installed.
+///
+/// Once part one is accepted, the puzzle is downloaded again to get part two.
 fn submit_result<T: Display>(
     result: T,
     day: Day,
@@ -159,11 +303,23 @@ fn submit_result<T: Display>(
         return None;
     }
 
+    if InputSource::from_args(&args) != Ok(InputSource::Puzzle) {
+        eprintln!("Not submitting: the answer was not computed from the puzzle input.");
+        return None;
+    }
+
     if aoc_cli::check().is_err() {
         eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
         process::exit(1);
     }
 
     println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    let submission = aoc_cli::submit(day, part, &result.to_string());
+
+    // part two is unlocked, fetch its description and example.
+    if part == 1 && submission.as_ref().is_ok_and(aoc_cli::is_accepted) {
+        example::handle_part_two(day);
+    }
+
+    Some(submission)
 }
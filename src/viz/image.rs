@@ -0,0 +1,408 @@
+/// Export of frames as image files.
+///
+/// PPM and PGM are written by hand and are always available. PNG and animated GIF need the
+/// `image-export` feature.
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    fs::File,
+    hash::Hash,
+    io::{self, BufWriter, Write},
+    path::Path,
+    str::FromStr,
+};
+
+use super::{Color, Frame};
+use crate::Map2D;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl Rgb {
+    pub const BLACK: Rgb = Rgb(0, 0, 0);
+    pub const WHITE: Rgb = Rgb(255, 255, 255);
+
+    /// Perceived brightness, used for grayscale output.
+    pub fn luma(&self) -> u8 {
+        let Rgb(r, g, b) = *self;
+        ((299 * u32::from(r) + 587 * u32::from(g) + 114 * u32::from(b)) / 1000) as u8
+    }
+}
+
+impl From<Color> for Rgb {
+    fn from(color: Color) -> Self {
+        match color {
+            Color::Black => Rgb(0, 0, 0),
+            Color::Red => Rgb(205, 49, 49),
+            Color::Green => Rgb(13, 188, 121),
+            Color::Yellow => Rgb(229, 229, 16),
+            Color::Blue => Rgb(36, 114, 200),
+            Color::Magenta => Rgb(188, 63, 188),
+            Color::Cyan => Rgb(17, 168, 205),
+            Color::White => Rgb(229, 229, 229),
+            Color::Gray => Rgb(102, 102, 102),
+        }
+    }
+}
+
+/// Maps tiles to colors, tiles without an explicit entry use the default color.
+#[derive(Debug, Clone)]
+pub struct Palette<T> {
+    colors: HashMap<T, Rgb>,
+    default: Rgb,
+}
+
+impl<T: Eq + Hash> Palette<T> {
+    pub fn new(default: Rgb) -> Palette<T> {
+        Palette {
+            colors: HashMap::new(),
+            default,
+        }
+    }
+
+    pub fn with(mut self, tile: T, color: Rgb) -> Palette<T> {
+        self.colors.insert(tile, color);
+        self
+    }
+
+    pub fn get(&self, tile: &T) -> Rgb {
+        self.colors.get(tile).copied().unwrap_or(self.default)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageFormat {
+    Ppm,
+    Pgm,
+    Png,
+    Gif,
+}
+
+impl ImageFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ImageFormat::Ppm => "ppm",
+            ImageFormat::Pgm => "pgm",
+            ImageFormat::Png => "png",
+            ImageFormat::Gif => "gif",
+        }
+    }
+
+    /// Whether the format needs the `image-export` feature.
+    pub fn needs_feature(&self) -> bool {
+        matches!(self, ImageFormat::Png | ImageFormat::Gif)
+    }
+}
+
+impl Display for ImageFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.extension())
+    }
+}
+
+impl FromStr for ImageFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "ppm" => Ok(ImageFormat::Ppm),
+            "pgm" => Ok(ImageFormat::Pgm),
+            "png" => Ok(ImageFormat::Png),
+            "gif" => Ok(ImageFormat::Gif),
+            _ => Err(format!(
+                "unknown image format `{s}`, expecting one of: ppm, pgm, png, gif"
+            )),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum ExportError {
+    IO(io::Error),
+    FeatureDisabled(ImageFormat),
+    #[cfg(feature = "image-export")]
+    Png(png::EncodingError),
+    #[cfg(feature = "image-export")]
+    Gif(gif::EncodingError),
+}
+
+impl Display for ExportError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ExportError::IO(e) => write!(f, "{e}"),
+            ExportError::FeatureDisabled(format) => write!(
+                f,
+                "{format} export requires the `image-export` feature to be enabled."
+            ),
+            #[cfg(feature = "image-export")]
+            ExportError::Png(e) => write!(f, "{e}"),
+            #[cfg(feature = "image-export")]
+            ExportError::Gif(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl From<io::Error> for ExportError {
+    fn from(e: io::Error) -> Self {
+        ExportError::IO(e)
+    }
+}
+
+#[cfg(feature = "image-export")]
+impl From<png::EncodingError> for ExportError {
+    fn from(e: png::EncodingError) -> Self {
+        ExportError::Png(e)
+    }
+}
+
+#[cfg(feature = "image-export")]
+impl From<gif::EncodingError> for ExportError {
+    fn from(e: gif::EncodingError) -> Self {
+        ExportError::Gif(e)
+    }
+}
+
+/// An RGB raster, one pixel per tile unless scaled.
+#[derive(Debug, Clone)]
+pub struct Image {
+    pub width: usize,
+    pub height: usize,
+    pub pixels: Vec<Rgb>,
+}
+
+impl Image {
+    /// Colors each tile of `map` with `palette`.
+    pub fn from_grid<T: Eq + Hash>(map: &Map2D<T>, palette: &Palette<T>) -> Image {
+        Image {
+            width: map.width,
+            height: map.height,
+            pixels: map.tiles.iter().map(|tile| palette.get(tile)).collect(),
+        }
+    }
+
+    /// Converts a terminal frame, cells without color are drawn in black.
+    pub fn from_frame(frame: &Frame) -> Image {
+        Image {
+            width: frame.cells.width,
+            height: frame.cells.height,
+            pixels: frame
+                .cells
+                .tiles
+                .iter()
+                .map(|cell| cell.color.map_or(Rgb::BLACK, Rgb::from))
+                .collect(),
+        }
+    }
+
+    /// Converts a terminal frame, coloring cells by symbol with `palette` instead of their color.
+    pub fn from_frame_with_palette(frame: &Frame, palette: &Palette<char>) -> Image {
+        Image::from_grid(&frame.cells.map(|cell| cell.symbol), palette)
+    }
+
+    /// Enlarges every pixel to a `factor` x `factor` square.
+    pub fn scaled(&self, factor: usize) -> Image {
+        let width = self.width * factor;
+        let height = self.height * factor;
+        let pixels = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x / factor, y / factor)))
+            .map(|(x, y)| self.pixels[y * self.width + x])
+            .collect();
+
+        Image {
+            width,
+            height,
+            pixels,
+        }
+    }
+
+    fn rgb_bytes(&self) -> Vec<u8> {
+        self.pixels
+            .iter()
+            .flat_map(|&Rgb(r, g, b)| [r, g, b])
+            .collect()
+    }
+
+    /// Encodes the image as a binary PPM (P6).
+    pub fn encode_ppm(&self, out: &mut impl Write) -> io::Result<()> {
+        write!(out, "P6\n{} {}\n255\n", self.width, self.height)?;
+        out.write_all(&self.rgb_bytes())
+    }
+
+    /// Encodes the image as a binary grayscale PGM (P5).
+    pub fn encode_pgm(&self, out: &mut impl Write) -> io::Result<()> {
+        write!(out, "P5\n{} {}\n255\n", self.width, self.height)?;
+        let bytes: Vec<u8> = self.pixels.iter().map(Rgb::luma).collect();
+        out.write_all(&bytes)
+    }
+
+    pub fn write_ppm(&self, path: &Path) -> Result<(), ExportError> {
+        let mut file = BufWriter::new(File::create(path)?);
+        self.encode_ppm(&mut file)?;
+        Ok(file.flush()?)
+    }
+
+    pub fn write_pgm(&self, path: &Path) -> Result<(), ExportError> {
+        let mut file = BufWriter::new(File::create(path)?);
+        self.encode_pgm(&mut file)?;
+        Ok(file.flush()?)
+    }
+
+    #[cfg(feature = "image-export")]
+    pub fn write_png(&self, path: &Path) -> Result<(), ExportError> {
+        let file = BufWriter::new(File::create(path)?);
+        let mut encoder = png::Encoder::new(file, self.width as u32, self.height as u32);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+
+        let mut writer = encoder.write_header()?;
+        writer.write_image_data(&self.rgb_bytes())?;
+        Ok(writer.finish()?)
+    }
+
+    #[cfg(not(feature = "image-export"))]
+    pub fn write_png(&self, _path: &Path) -> Result<(), ExportError> {
+        Err(ExportError::FeatureDisabled(ImageFormat::Png))
+    }
+
+    /// Writes a single image in the given format. GIF images are written as a one-frame animation.
+    pub fn write(&self, path: &Path, format: ImageFormat) -> Result<(), ExportError> {
+        match format {
+            ImageFormat::Ppm => self.write_ppm(path),
+            ImageFormat::Pgm => self.write_pgm(path),
+            ImageFormat::Png => self.write_png(path),
+            ImageFormat::Gif => {
+                let mut recorder = GifRecorder::create(path, self.width, self.height, 0)?;
+                recorder.add_frame(self)
+            }
+        }
+    }
+}
+
+/// Appends images as frames of an animated GIF.
+pub struct GifRecorder {
+    #[cfg(feature = "image-export")]
+    encoder: gif::Encoder<BufWriter<File>>,
+    #[cfg(feature = "image-export")]
+    width: usize,
+    #[cfg(feature = "image-export")]
+    height: usize,
+    /// Delay between frames, in hundredths of a second.
+    #[cfg(feature = "image-export")]
+    delay: u16,
+}
+
+impl GifRecorder {
+    #[cfg(feature = "image-export")]
+    pub fn create(
+        path: &Path,
+        width: usize,
+        height: usize,
+        delay: u16,
+    ) -> Result<GifRecorder, ExportError> {
+        let file = BufWriter::new(File::create(path)?);
+        let mut encoder = gif::Encoder::new(file, width as u16, height as u16, &[])?;
+        encoder.set_repeat(gif::Repeat::Infinite)?;
+
+        Ok(GifRecorder {
+            encoder,
+            width,
+            height,
+            delay,
+        })
+    }
+
+    #[cfg(not(feature = "image-export"))]
+    pub fn create(
+        _path: &Path,
+        _width: usize,
+        _height: usize,
+        _delay: u16,
+    ) -> Result<GifRecorder, ExportError> {
+        Err(ExportError::FeatureDisabled(ImageFormat::Gif))
+    }
+
+    /// Adds a frame to the animation. Frames must all have the size given on creation.
+    #[cfg(feature = "image-export")]
+    pub fn add_frame(&mut self, image: &Image) -> Result<(), ExportError> {
+        if image.width != self.width || image.height != self.height {
+            return Err(ExportError::IO(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "all frames of a GIF must have the same size",
+            )));
+        }
+
+        let mut frame = gif::Frame::from_rgb_speed(
+            self.width as u16,
+            self.height as u16,
+            &image.rgb_bytes(),
+            10,
+        );
+        frame.delay = self.delay;
+        Ok(self.encoder.write_frame(&frame)?)
+    }
+
+    #[cfg(not(feature = "image-export"))]
+    pub fn add_frame(&mut self, _image: &Image) -> Result<(), ExportError> {
+        Err(ExportError::FeatureDisabled(ImageFormat::Gif))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::viz::Cell;
+
+    fn two_pixels() -> Image {
+        Image {
+            width: 2,
+            height: 1,
+            pixels: vec![Rgb(255, 0, 0), Rgb::WHITE],
+        }
+    }
+
+    #[test]
+    fn encodes_netpbm_images() {
+        let mut ppm = vec![];
+        two_pixels().encode_ppm(&mut ppm).unwrap();
+        assert_eq!(ppm, b"P6\n2 1\n255\n\xff\x00\x00\xff\xff\xff");
+
+        let mut pgm = vec![];
+        two_pixels().encode_pgm(&mut pgm).unwrap();
+        assert_eq!(pgm, b"P5\n2 1\n255\n\x4c\xff");
+    }
+
+    #[test]
+    fn scales_pixels_to_squares() {
+        let image = two_pixels().scaled(2);
+        assert_eq!((image.width, image.height), (4, 2));
+        assert_eq!(
+            image.pixels,
+            [[Rgb(255, 0, 0); 2], [Rgb::WHITE; 2]].concat().repeat(2)
+        );
+    }
+
+    #[test]
+    fn colors_frames() {
+        let frame = Frame {
+            cells: Map2D {
+                width: 3,
+                height: 1,
+                tiles: vec![
+                    Cell::new('#', Color::Red),
+                    Cell::plain('.'),
+                    Cell::plain('O'),
+                ],
+            },
+        };
+        assert_eq!(
+            Image::from_frame(&frame).pixels,
+            vec![Rgb::from(Color::Red), Rgb::BLACK, Rgb::BLACK]
+        );
+
+        let palette = Palette::new(Rgb::BLACK).with('O', Rgb(0, 0, 255));
+        assert_eq!(
+            Image::from_frame_with_palette(&frame, &palette).pixels,
+            vec![Rgb::BLACK, Rgb::BLACK, Rgb(0, 0, 255)]
+        );
+    }
+}
@@ -0,0 +1,484 @@
+/// Terminal visualization of grid simulations.
+///
+/// Solutions create a [`Visualizer`] and feed it [`Frame`]s. Nothing is rendered unless the
+/// binary was started with `--visualize` (e.g. `cargo solve 14 --visualize`), so the calls can
+/// stay in the solution code without affecting tests or benchmarks.
+///
+/// Frames can also be exported as images to `data/viz/NN/` with `--export <format>`,
+/// see [`image`] for the supported formats. Solutions working on graphs can write diagrams
+/// (e.g. Graphviz DOT or Mermaid) to the same directory with `--diagram`.
+use std::{
+    env,
+    fmt::Display,
+    fs::{self, File},
+    io::{self, stdin, stdout, BufWriter, Write},
+    path::PathBuf,
+    thread,
+    time::Duration,
+};
+
+use crate::template::{paths, Day, ANSI_BOLD, ANSI_CLEAR_SCREEN, ANSI_RESET};
+use crate::{Map2D, Pos2D};
+
+pub mod image;
+
+use image::{GifRecorder, Image, ImageFormat, Palette};
+
+const DEFAULT_FPS: u32 = 10;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Color {
+    Black,
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+    Gray,
+}
+
+impl Color {
+    fn ansi_code(&self) -> &'static str {
+        match self {
+            Color::Black => "\x1b[30m",
+            Color::Red => "\x1b[31m",
+            Color::Green => "\x1b[32m",
+            Color::Yellow => "\x1b[33m",
+            Color::Blue => "\x1b[34m",
+            Color::Magenta => "\x1b[35m",
+            Color::Cyan => "\x1b[36m",
+            Color::White => "\x1b[37m",
+            Color::Gray => "\x1b[90m",
+        }
+    }
+}
+
+/// A single rendered tile.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cell {
+    pub symbol: char,
+    pub color: Option<Color>,
+}
+
+impl Cell {
+    pub fn new(symbol: char, color: Color) -> Cell {
+        Cell {
+            symbol,
+            color: Some(color),
+        }
+    }
+
+    pub fn plain(symbol: char) -> Cell {
+        Cell {
+            symbol,
+            color: None,
+        }
+    }
+}
+
+/// A snapshot of a simulation, ready to be rendered.
+#[derive(Debug, Clone)]
+pub struct Frame {
+    pub cells: Map2D<Cell>,
+}
+
+impl Frame {
+    /// Renders each tile of `map` with `paint`.
+    pub fn from_grid<T>(map: &Map2D<T>, paint: impl Fn(&Pos2D, &T) -> Cell) -> Frame {
+        let tiles = map
+            .tiles
+            .iter()
+            .enumerate()
+            .map(|(idx, tile)| paint(&map.idx2pos(idx), tile))
+            .collect();
+
+        Frame {
+            cells: Map2D {
+                width: map.width,
+                height: map.height,
+                tiles,
+            },
+        }
+    }
+
+    /// Renders `positions` with `cell` on an empty background of the given size.
+    /// Positions outside of the frame are ignored.
+    pub fn from_positions<'a>(
+        width: usize,
+        height: usize,
+        positions: impl IntoIterator<Item = &'a Pos2D>,
+        cell: Cell,
+    ) -> Frame {
+        let mut cells = Map2D {
+            width,
+            height,
+            tiles: vec![Cell::plain('.'); width * height],
+        };
+
+        for position in positions {
+            if let Some(idx) = cells.pos2idx(position) {
+                cells.tiles[idx] = cell;
+            }
+        }
+
+        Frame { cells }
+    }
+
+    /// Returns the frame as plain text, without colors.
+    pub fn to_plain_string(&self) -> String {
+        self.cells
+            .rows()
+            .map(|row| row.map(|cell| cell.symbol).collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Returns the frame as text with ANSI color escapes.
+    pub fn to_ansi_string(&self) -> String {
+        self.cells
+            .rows()
+            .map(|row| {
+                row.map(|cell| match cell.color {
+                    Some(color) => format!("{}{}{ANSI_RESET}", color.ansi_code(), cell.symbol),
+                    None => cell.symbol.to_string(),
+                })
+                .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+/// Visualization settings, read from the command-line arguments of a solution binary.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Options {
+    pub enabled: bool,
+    pub fps: Option<u32>,
+    /// Wait for <Enter> between frames instead of using the frame rate.
+    pub step: bool,
+    /// Write every frame to `data/viz/NN/frames.txt`.
+    pub dump: bool,
+    /// Write every frame as an image to `data/viz/NN/`. Works without `--visualize`.
+    pub export: Option<ImageFormat>,
+    /// Size in pixels of a tile in exported images.
+    pub scale: Option<usize>,
+    /// Write diagrams to `data/viz/NN/`. Works without `--visualize`.
+    pub diagram: bool,
+}
+
+impl Options {
+    pub fn from_args(args: &[String]) -> Options {
+        let value_of = |flag: &str| {
+            args.iter()
+                .position(|x| x == flag)
+                .and_then(|idx| args.get(idx + 1))
+        };
+
+        // benchmarks run the solution many times, never render nor export while timing.
+        let timing = args.contains(&"--time".into());
+
+        Options {
+            enabled: args.contains(&"--visualize".into()) && !timing,
+            fps: value_of("--fps").and_then(|fps| fps.parse().ok()),
+            step: args.contains(&"--step".into()),
+            dump: args.contains(&"--dump".into()),
+            export: value_of("--export")
+                .and_then(|format| format.parse().ok())
+                .filter(|_| !timing),
+            scale: value_of("--scale").and_then(|scale| scale.parse().ok()),
+            diagram: args.contains(&"--diagram".into()) && !timing,
+        }
+    }
+
+    /// Converts the options back to arguments, so they can be forwarded to a child command.
+    pub fn to_args(&self) -> Vec<String> {
+        let mut args = vec![];
+        if let Some(format) = self.export {
+            args.push("--export".into());
+            args.push(format.to_string());
+        }
+        if let Some(scale) = self.scale {
+            args.push("--scale".into());
+            args.push(scale.to_string());
+        }
+        if self.diagram {
+            args.push("--diagram".into());
+        }
+
+        if !self.enabled {
+            return args;
+        }
+
+        args.push("--visualize".into());
+        if let Some(fps) = self.fps {
+            args.push("--fps".into());
+            args.push(fps.to_string());
+        }
+        if self.step {
+            args.push("--step".into());
+        }
+        if self.dump {
+            args.push("--dump".into());
+        }
+        args
+    }
+}
+
+/// Renders frames to the terminal and optionally to a text file.
+pub struct Visualizer {
+    day: Day,
+    options: Options,
+    dump_file: Option<BufWriter<File>>,
+    gif: Option<GifRecorder>,
+    /// Colors of exported images by cell symbol, instead of the cell colors.
+    palette: Option<Palette<char>>,
+    frame_count: usize,
+}
+
+impl Visualizer {
+    pub fn new(day: Day, options: Options) -> Visualizer {
+        let dump_file = if options.enabled && options.dump {
+            match create_dump_file(day) {
+                Ok(file) => Some(file),
+                Err(e) => {
+                    eprintln!("Failed to create visualization dump file: {e}");
+                    None
+                }
+            }
+        } else {
+            None
+        };
+
+        Visualizer {
+            day,
+            options,
+            dump_file,
+            gif: None,
+            palette: None,
+            frame_count: 0,
+        }
+    }
+
+    /// Colors exported images with `palette`, by cell symbol.
+    pub fn with_palette(mut self, palette: Palette<char>) -> Visualizer {
+        self.palette = Some(palette);
+        self
+    }
+
+    /// Creates a visualizer configured from the arguments of the current process.
+    pub fn from_args(day: Day) -> Visualizer {
+        let args: Vec<String> = env::args().collect();
+        Visualizer::new(day, Options::from_args(&args))
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.options.enabled || self.options.export.is_some()
+    }
+
+    /// Renders a frame, then waits according to the frame rate or step mode.
+    pub fn show(&mut self, frame: &Frame, title: impl Display) {
+        if !self.is_enabled() {
+            return;
+        }
+
+        self.frame_count += 1;
+
+        if let Some(format) = self.options.export {
+            if let Err(e) = self.export_frame(frame, format) {
+                eprintln!("Failed to export visualization frame: {e}");
+                self.options.export = None;
+            }
+        }
+
+        if !self.options.enabled {
+            return;
+        }
+
+        if let Some(file) = self.dump_file.as_mut() {
+            let written = writeln!(
+                file,
+                "=== Frame {}: {title}\n{}\n",
+                self.frame_count,
+                frame.to_plain_string()
+            );
+            if let Err(e) = written {
+                eprintln!("Failed to write visualization frame: {e}");
+                self.dump_file = None;
+            }
+        }
+
+        let mut stdout = stdout();
+        print!("{ANSI_CLEAR_SCREEN}");
+        println!("{ANSI_BOLD}Frame {}{ANSI_RESET}: {title}", self.frame_count);
+        println!("{}", frame.to_ansi_string());
+        let _ = stdout.flush();
+
+        if self.options.step {
+            println!("Press <Enter> for the next frame...");
+            let _ = stdin().read_line(&mut String::new());
+        } else {
+            let fps = self.options.fps.unwrap_or(DEFAULT_FPS).max(1);
+            thread::sleep(Duration::from_secs_f64(1.0 / f64::from(fps)));
+        }
+    }
+
+    /// Writes a diagram to `data/viz/NN/<file_name>` when enabled with `--diagram`.
+    /// The diagram is only rendered if needed.
+    pub fn export_diagram<E: Display>(
+        &self,
+        file_name: &str,
+        render: impl FnOnce() -> Result<String, E>,
+    ) {
+        if !self.options.diagram {
+            return;
+        }
+
+        let diagram = match render() {
+            Ok(diagram) => diagram,
+            Err(e) => {
+                eprintln!("Failed to render diagram \"{file_name}\": {e}");
+                return;
+            }
+        };
+
+        let written = output_dir(self.day).and_then(|dir| {
+            let path = dir.join(file_name);
+            fs::write(&path, diagram)?;
+            Ok(path)
+        });
+        match written {
+            Ok(path) => println!("Wrote diagram to \"{}\"", path.display()),
+            Err(e) => eprintln!("Failed to write diagram: {e}"),
+        }
+    }
+
+    /// Converts a frame for export, with the palette and scale.
+    fn to_image(&self, frame: &Frame) -> Image {
+        let image = match &self.palette {
+            Some(palette) => Image::from_frame_with_palette(frame, palette),
+            None => Image::from_frame(frame),
+        };
+        image.scaled(self.options.scale.unwrap_or(1).max(1))
+    }
+
+    /// Writes the frame to its own file, or appends it to the day's animation for GIFs.
+    fn export_frame(
+        &mut self,
+        frame: &Frame,
+        format: ImageFormat,
+    ) -> Result<(), image::ExportError> {
+        let image = self.to_image(frame);
+        let dir = output_dir(self.day)?;
+
+        if format != ImageFormat::Gif {
+            let path = dir.join(format!("frame_{:05}.{format}", self.frame_count));
+            return image.write(&path, format);
+        }
+
+        if self.gif.is_none() {
+            let path = dir.join("animation.gif");
+            let delay = 100 / self.options.fps.unwrap_or(DEFAULT_FPS).clamp(1, 100);
+            println!("Writing visualization animation to \"{}\"", path.display());
+            self.gif = Some(GifRecorder::create(
+                &path,
+                image.width,
+                image.height,
+                delay as u16,
+            )?);
+        }
+
+        self.gif.as_mut().unwrap().add_frame(&image)
+    }
+}
+
+/// Returns the visualization output directory of a day, creating it if needed.
+pub fn output_dir(day: Day) -> Result<PathBuf, io::Error> {
+    let dir = paths::viz_dir(day);
+    fs::create_dir_all(&dir)?;
+    Ok(dir)
+}
+
+fn create_dump_file(day: Day) -> Result<BufWriter<File>, io::Error> {
+    let path = output_dir(day)?.join("frames.txt");
+    println!("Writing visualization frames to \"{}\"", path.display());
+    Ok(BufWriter::new(File::create(path)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn forwards_options_as_arguments() {
+        let options = Options {
+            enabled: true,
+            fps: Some(30),
+            step: true,
+            dump: true,
+            export: Some(ImageFormat::Ppm),
+            scale: Some(4),
+            diagram: true,
+        };
+        assert_eq!(Options::from_args(&options.to_args()), options);
+
+        // only the exports are forwarded when the terminal rendering is off.
+        let options = Options {
+            fps: None,
+            step: false,
+            dump: false,
+            enabled: false,
+            ..options
+        };
+        assert_eq!(
+            options.to_args(),
+            args(&["--export", "ppm", "--scale", "4", "--diagram"])
+        );
+        assert_eq!(Options::from_args(&options.to_args()), options);
+    }
+
+    #[test]
+    fn never_renders_while_timing() {
+        let options = Options::from_args(&args(&[
+            "--visualize",
+            "--diagram",
+            "--export",
+            "ppm",
+            "--time",
+        ]));
+        assert!(!options.enabled);
+        assert!(!options.diagram);
+        assert_eq!(options.export, None);
+        assert_eq!(options.to_args(), Vec::<String>::new());
+    }
+
+    #[test]
+    fn exports_scaled_images_with_palette() {
+        let options = Options::from_args(&args(&["--export", "ppm", "--scale", "3"]));
+        let visualizer = Visualizer::new(Day::new(14).unwrap(), options)
+            .with_palette(Palette::new(image::Rgb::BLACK).with('#', image::Rgb::WHITE));
+
+        let frame = Frame::from_positions(2, 1, &[Pos2D::new(1, 0)], Cell::plain('#'));
+        let image = visualizer.to_image(&frame);
+        assert_eq!((image.width, image.height), (6, 3));
+        assert_eq!(image.pixels[0], image::Rgb::BLACK);
+        assert_eq!(image.pixels[5], image::Rgb::WHITE);
+    }
+
+    #[test]
+    fn renders_frames_as_text() {
+        let positions = [Pos2D::new(0, 0), Pos2D::new(2, 1), Pos2D::new(5, 5)];
+        let frame = Frame::from_positions(3, 2, &positions, Cell::new('#', Color::Green));
+
+        assert_eq!(frame.to_plain_string(), "#..\n..#");
+        assert_eq!(
+            frame.to_ansi_string(),
+            format!("\x1b[32m#{ANSI_RESET}..\n..\x1b[32m#{ANSI_RESET}")
+        );
+    }
+}